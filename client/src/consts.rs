@@ -1,2 +1 @@
 pub const PROGRAM_ID: &str = "AsTmgPMPEEM31c1Pasv9Dbqtg8iJ3NBYeyz8xoZ3kaqK";
-pub const RENT: &str = "SysvarRent111111111111111111111111111111111";
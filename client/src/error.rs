@@ -0,0 +1,30 @@
+use betting::error::ContractError;
+use num_traits::FromPrimitive;
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+/// Message of the program's `ContractError` with the given custom error code.
+pub fn contract_error(code: u32) -> Option<String> {
+    ContractError::from_u32(code).map(|err| err.to_string())
+}
+
+pub fn send_transaction(client: &RpcClient, tx: &Transaction) -> Signature {
+    client
+        .send_transaction(tx)
+        .unwrap_or_else(|err| panic!("Transaction failed. {}", describe(&err)))
+}
+
+fn describe(err: &ClientError) -> String {
+    match err.get_transaction_error() {
+        Some(TransactionError::InstructionError(_, InstructionError::Custom(code))) => {
+            match contract_error(code) {
+                Some(message) => format!("{} (code {})", message, code),
+                None => format!("Unknown custom error {}", code),
+            }
+        }
+        _ => err.to_string(),
+    }
+}
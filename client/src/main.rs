@@ -1,20 +1,39 @@
 mod consts;
-mod structs;
+mod error;
 mod transactions;
 
 use crate::transactions::add_bot::add_bot;
 use crate::transactions::add_supported_token::add_supported_token;
-use crate::transactions::close_game::close_game;
+use crate::transactions::ban::{ban_user, unban_user};
+use crate::transactions::bots::{bots, remove_bot, suspend_bot};
+use crate::transactions::claim_referral_rewards::claim_referral_rewards;
+use crate::transactions::config_change::{
+    cancel_config_change, execute_config_change, queue_config_change,
+};
+use crate::transactions::emergency::{emergency_refund, emergency_shutdown};
+use crate::transactions::events::events;
 use crate::transactions::forced_close::forced_close;
 use crate::transactions::init::init;
 use crate::transactions::join_game::join_game;
 use crate::transactions::manually_close::manually_close;
+use crate::transactions::migrate_account::migrate_account;
 use crate::transactions::new_game::new_game;
+use crate::transactions::pause::{set_pause_flags, set_token_paused, set_type_paused};
+use crate::transactions::profile::{
+    change_referrer, deregister, set_credential_hash, set_limits, update_profile,
+};
+use crate::transactions::referral_codes::{
+    claim_referral_code, referral_code, release_referral_code, transfer_referral_code,
+};
 use crate::transactions::registration::registration;
+use crate::transactions::roles::{grant_role, revoke_role};
+use crate::transactions::sessions::{create_session, revoke_session};
 use crate::transactions::setters::{
-    lock_bets, new_delay, new_manager, set_admin_fee, set_global_fee, set_transaction_fee,
-    set_type_price, set_winner_fee, unlock_bets,
+    accept_admin, lock_bets, propose_admin, set_type_price, unlock_bets,
 };
+use crate::transactions::settle_game::{distribute, settle_game};
+use crate::transactions::token_stats::token_stats;
+use crate::transactions::user_stats::user_stats;
 use clap::{
     app_from_crate, crate_authors, crate_description, crate_name, crate_version, Arg, SubCommand,
 };
@@ -38,9 +57,9 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("manager")
-                        .short("m")
-                        .long("manager")
+                    Arg::with_name("admin")
+                        .short("a")
+                        .long("admin")
                         .required(false)
                         .takes_value(true),
                 )
@@ -53,7 +72,7 @@ fn main() {
                 ),
         )
         .subcommand(
-            SubCommand::with_name("change_close_delay")
+            SubCommand::with_name("lock_bets")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -67,17 +86,10 @@ fn main() {
                         .long("env")
                         .required(false)
                         .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("new_delay")
-                        .short("d")
-                        .long("new_delay")
-                        .required(false)
-                        .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("lock_bets")
+            SubCommand::with_name("unlock_bets")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -94,7 +106,7 @@ fn main() {
                 ),
         )
         .subcommand(
-            SubCommand::with_name("unlock_bets")
+            SubCommand::with_name("set_type_price")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -108,10 +120,24 @@ fn main() {
                         .long("env")
                         .required(false)
                         .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("type")
+                        .short("t")
+                        .long("type")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("price")
+                        .short("p")
+                        .long("price")
+                        .required(false)
+                        .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("new_manager")
+            SubCommand::with_name("add_supported_token")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -127,15 +153,15 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("manager")
-                        .short("m")
-                        .long("manager")
+                    Arg::with_name("token")
+                        .short("t")
+                        .long("token")
                         .required(false)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("set_global_fee")
+            SubCommand::with_name("registration")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -151,15 +177,38 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("fee")
-                        .short("f")
-                        .long("fee")
+                    Arg::with_name("referrer")
+                        .short("r")
+                        .long("referrer")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("code")
+                        .short("c")
+                        .long("code")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("password")
+                        .short("p")
+                        .long("password")
+                        .required(false)
+                        .requires("salt")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("salt")
+                        .short("k")
+                        .long("salt")
                         .required(false)
+                        .requires("password")
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("set_admin_fee")
+            SubCommand::with_name("add_bot")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -175,15 +224,36 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("fee")
-                        .short("f")
-                        .long("fee")
+                    Arg::with_name("bot")
+                        .short("b")
+                        .long("bot")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("max_games")
+                        .short("g")
+                        .long("max_games")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("max_type_price")
+                        .short("p")
+                        .long("max_type_price")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("daily_volume")
+                        .short("v")
+                        .long("daily_volume")
                         .required(false)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("set_winner_fee")
+            SubCommand::with_name("new_game")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -199,15 +269,22 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("fee")
-                        .short("f")
-                        .long("fee")
+                    Arg::with_name("value")
+                        .short("v")
+                        .long("value")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("user")
+                        .short("u")
+                        .long("user")
                         .required(false)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("set_transaction_fee")
+            SubCommand::with_name("forced_close")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -223,15 +300,32 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("fee")
-                        .short("f")
-                        .long("fee")
+                    Arg::with_name("user")
+                        .short("u")
+                        .long("user")
                         .required(false)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("set_type_price")
+            SubCommand::with_name("manually_close")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("join_game")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -247,22 +341,29 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("type")
-                        .short("t")
-                        .long("type")
+                    Arg::with_name("master")
+                        .short("m")
+                        .long("master")
                         .required(false)
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("price")
-                        .short("p")
-                        .long("price")
+                    Arg::with_name("value")
+                        .short("v")
+                        .long("value")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("user")
+                        .short("u")
+                        .long("user")
                         .required(false)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("add_supported_token")
+            SubCommand::with_name("settle_game")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -278,15 +379,29 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("token")
+                    Arg::with_name("user")
+                        .short("u")
+                        .long("user")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("winner")
+                        .short("w")
+                        .long("winner")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("type")
                         .short("t")
-                        .long("token")
+                        .long("type")
                         .required(false)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("registration")
+            SubCommand::with_name("distribute")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -302,29 +417,39 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("referrer")
-                        .short("r")
-                        .long("referrer")
-                        .required(false)
+                    Arg::with_name("user")
+                        .short("u")
+                        .long("user")
+                        .required(true)
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("password")
-                        .short("p")
-                        .long("password")
-                        .required(false)
+                    Arg::with_name("index")
+                        .short("i")
+                        .long("index")
+                        .required(true)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("add_bot")
+            SubCommand::with_name("token_stats")
                 .arg(
-                    Arg::with_name("sign")
-                        .short("s")
-                        .long("sign")
-                        .required(true)
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
                         .takes_value(true),
                 )
+                .arg(
+                    Arg::with_name("token")
+                        .short("t")
+                        .long("token")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("events")
                 .arg(
                     Arg::with_name("env")
                         .short("e")
@@ -333,15 +458,15 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("bot")
-                        .short("b")
-                        .long("bot")
-                        .required(false)
+                    Arg::with_name("transaction")
+                        .short("t")
+                        .long("transaction")
+                        .required(true)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("new_game")
+            SubCommand::with_name("claim_referral_rewards")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -357,15 +482,15 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("value")
-                        .short("v")
-                        .long("value")
-                        .required(false)
+                    Arg::with_name("token")
+                        .short("t")
+                        .long("token")
+                        .required(true)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("forced_close")
+            SubCommand::with_name("claim_referral_code")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -381,15 +506,15 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("user")
-                        .short("u")
-                        .long("user")
-                        .required(false)
+                    Arg::with_name("code")
+                        .short("c")
+                        .long("code")
+                        .required(true)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("manually_close")
+            SubCommand::with_name("transfer_referral_code")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -403,10 +528,24 @@ fn main() {
                         .long("env")
                         .required(false)
                         .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("code")
+                        .short("c")
+                        .long("code")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("owner")
+                        .short("o")
+                        .long("owner")
+                        .required(true)
+                        .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("join_game")
+            SubCommand::with_name("release_referral_code")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -422,22 +561,32 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("master")
-                        .short("m")
-                        .long("master")
+                    Arg::with_name("code")
+                        .short("c")
+                        .long("code")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("referral_code")
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
                         .required(false)
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("value")
-                        .short("v")
-                        .long("value")
-                        .required(false)
+                    Arg::with_name("code")
+                        .short("c")
+                        .long("code")
+                        .required(true)
                         .takes_value(true),
                 ),
         )
         .subcommand(
-            SubCommand::with_name("close_game")
+            SubCommand::with_name("propose_admin")
                 .arg(
                     Arg::with_name("sign")
                         .short("s")
@@ -453,63 +602,773 @@ fn main() {
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("user")
-                        .short("u")
-                        .long("user")
-                        .required(false)
+                    Arg::with_name("admin")
+                        .short("a")
+                        .long("admin")
+                        .required(true)
                         .takes_value(true),
-                )
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("accept_admin")
                 .arg(
-                    Arg::with_name("winner")
-                        .short("w")
-                        .long("winner")
-                        .required(false)
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
                         .takes_value(true),
                 )
                 .arg(
-                    Arg::with_name("type")
-                        .short("t")
-                        .long("type")
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
                         .required(false)
                         .takes_value(true),
                 ),
         )
-        .get_matches();
-
-    if let Some(matches) = matches.subcommand_matches("init") {
-        init(matches);
-    }
-
-    if let Some(matches) = matches.subcommand_matches("change_close_delay") {
-        new_delay(matches);
-    }
-
-    if let Some(matches) = matches.subcommand_matches("lock_bets") {
-        lock_bets(matches);
-    }
-
-    if let Some(matches) = matches.subcommand_matches("unlock_bets") {
-        unlock_bets(matches);
-    }
-
-    if let Some(matches) = matches.subcommand_matches("new_manager") {
-        new_manager(matches);
-    }
-
-    if let Some(matches) = matches.subcommand_matches("set_global_fee") {
-        set_global_fee(matches);
-    }
+        .subcommand(
+            SubCommand::with_name("grant_role")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("address")
+                        .short("a")
+                        .long("address")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("roles")
+                        .short("r")
+                        .long("roles")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("revoke_role")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("address")
+                        .short("a")
+                        .long("address")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("roles")
+                        .short("r")
+                        .long("roles")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("queue_config_change")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("global_fee")
+                        .short("g")
+                        .long("global_fee")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("admin_fee")
+                        .short("a")
+                        .long("admin_fee")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("winner_fee")
+                        .short("w")
+                        .long("winner_fee")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("transaction_fee")
+                        .short("x")
+                        .long("transaction_fee")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("close_delay")
+                        .short("d")
+                        .long("close_delay")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("tiers")
+                        .short("t")
+                        .long("tiers")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("config_delay")
+                        .short("c")
+                        .long("config_delay")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("execute_config_change")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("cancel_config_change")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set_pause_flags")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("actions")
+                        .short("a")
+                        .long("actions")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set_token_paused")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("token")
+                        .short("t")
+                        .long("token")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("paused")
+                        .short("p")
+                        .long("paused")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set_type_paused")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("type")
+                        .short("t")
+                        .long("type")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("paused")
+                        .short("p")
+                        .long("paused")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove_bot")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("bot")
+                        .short("b")
+                        .long("bot")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("suspend_bot")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("bot")
+                        .short("b")
+                        .long("bot")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("suspended")
+                        .short("p")
+                        .long("suspended")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("bots").arg(
+                Arg::with_name("env")
+                    .short("e")
+                    .long("env")
+                    .required(false)
+                    .takes_value(true),
+            ),
+        )
+        .subcommand(
+            SubCommand::with_name("ban_user")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("user")
+                        .short("u")
+                        .long("user")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("reason")
+                        .short("r")
+                        .long("reason")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("expiry")
+                        .short("x")
+                        .long("expiry")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("unban_user")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("user")
+                        .short("u")
+                        .long("user")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("migrate_account")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("kind")
+                        .short("k")
+                        .long("kind")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("address")
+                        .short("a")
+                        .long("address")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("type")
+                        .short("t")
+                        .long("type")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("mint")
+                        .short("m")
+                        .long("mint")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("code")
+                        .short("c")
+                        .long("code")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("emergency_shutdown")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("shutdown")
+                        .short("x")
+                        .long("shutdown")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("emergency_refund")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("game")
+                        .short("g")
+                        .long("game")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set_credential_hash")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("password")
+                        .short("p")
+                        .long("password")
+                        .required(false)
+                        .requires("salt")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("salt")
+                        .short("k")
+                        .long("salt")
+                        .required(false)
+                        .requires("password")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("update_profile")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("nickname")
+                        .short("n")
+                        .long("nickname")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("avatar")
+                        .short("a")
+                        .long("avatar")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("change_referrer")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("referrer")
+                        .short("r")
+                        .long("referrer")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("code")
+                        .short("c")
+                        .long("code")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("deregister")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("user_stats")
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("user")
+                        .short("u")
+                        .long("user")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set_limits")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("max_type_price")
+                        .short("p")
+                        .long("max_type_price")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("max_daily_games")
+                        .short("g")
+                        .long("max_daily_games")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("daily_loss_limit")
+                        .short("l")
+                        .long("daily_loss_limit")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("excluded_until")
+                        .short("x")
+                        .long("excluded_until")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("create_session")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("delegate")
+                        .short("d")
+                        .long("delegate")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("expires_at")
+                        .short("x")
+                        .long("expires_at")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("max_stake_per_game")
+                        .short("p")
+                        .long("max_stake_per_game")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("max_total")
+                        .short("m")
+                        .long("max_total")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("token")
+                        .short("t")
+                        .long("token")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("allowance")
+                        .short("a")
+                        .long("allowance")
+                        .required(true)
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("revoke_session")
+                .arg(
+                    Arg::with_name("sign")
+                        .short("s")
+                        .long("sign")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("env")
+                        .short("e")
+                        .long("env")
+                        .required(false)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("token")
+                        .short("t")
+                        .long("token")
+                        .required(false)
+                        .takes_value(true),
+                ),
+        )
+        .get_matches();
 
-    if let Some(matches) = matches.subcommand_matches("set_admin_fee") {
-        set_admin_fee(matches);
+    if let Some(matches) = matches.subcommand_matches("init") {
+        init(matches);
     }
 
-    if let Some(matches) = matches.subcommand_matches("set_winner_fee") {
-        set_winner_fee(matches);
+    if let Some(matches) = matches.subcommand_matches("lock_bets") {
+        lock_bets(matches);
     }
 
-    if let Some(matches) = matches.subcommand_matches("set_transaction_fee") {
-        set_transaction_fee(matches);
+    if let Some(matches) = matches.subcommand_matches("unlock_bets") {
+        unlock_bets(matches);
     }
 
     if let Some(matches) = matches.subcommand_matches("set_type_price") {
@@ -544,7 +1403,143 @@ fn main() {
         join_game(matches);
     }
 
-    if let Some(matches) = matches.subcommand_matches("close_game") {
-        close_game(matches);
+    if let Some(matches) = matches.subcommand_matches("settle_game") {
+        settle_game(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("distribute") {
+        distribute(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("token_stats") {
+        token_stats(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("events") {
+        events(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("claim_referral_rewards") {
+        claim_referral_rewards(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("claim_referral_code") {
+        claim_referral_code(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("transfer_referral_code") {
+        transfer_referral_code(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("release_referral_code") {
+        release_referral_code(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("referral_code") {
+        referral_code(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("propose_admin") {
+        propose_admin(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("accept_admin") {
+        accept_admin(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("grant_role") {
+        grant_role(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("revoke_role") {
+        revoke_role(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("queue_config_change") {
+        queue_config_change(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("execute_config_change") {
+        execute_config_change(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("cancel_config_change") {
+        cancel_config_change(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("set_pause_flags") {
+        set_pause_flags(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("set_token_paused") {
+        set_token_paused(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("set_type_paused") {
+        set_type_paused(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("remove_bot") {
+        remove_bot(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("suspend_bot") {
+        suspend_bot(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("bots") {
+        bots(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("ban_user") {
+        ban_user(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("unban_user") {
+        unban_user(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("migrate_account") {
+        migrate_account(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("emergency_shutdown") {
+        emergency_shutdown(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("emergency_refund") {
+        emergency_refund(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("set_credential_hash") {
+        set_credential_hash(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("update_profile") {
+        update_profile(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("change_referrer") {
+        change_referrer(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("deregister") {
+        deregister(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("user_stats") {
+        user_stats(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("set_limits") {
+        set_limits(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("create_session") {
+        create_session(matches);
+    }
+
+    if let Some(matches) = matches.subcommand_matches("revoke_session") {
+        revoke_session(matches);
     }
 }
@@ -1,16 +1,15 @@
-use crate::consts::{PROGRAM_ID, RENT};
-use crate::structs::BettingInstruction;
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
 use clap::ArgMatches;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{read_keypair_file, Signer};
 #[allow(unused_imports)]
 use solana_sdk::signer::keypair::Keypair;
 #[allow(unused_imports)]
 use solana_sdk::signer::signers::Signers;
-use solana_sdk::system_program;
 use solana_sdk::transaction::Transaction;
 
 pub fn add_bot(matches: &ArgMatches) {
@@ -28,22 +27,24 @@ pub fn add_bot(matches: &ArgMatches) {
 
     let bot = matches.value_of("bot").unwrap().parse::<Pubkey>().unwrap();
 
-    let (user_pda, _) =
-        Pubkey::find_program_address(&["user".as_bytes(), &bot.to_bytes()], &program_id);
+    let limit = |name: &str| {
+        matches
+            .value_of(name)
+            .map(|value| value.parse::<u64>().unwrap())
+            .unwrap_or(0)
+    };
 
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::AddBot { bot },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new_readonly(RENT.parse::<Pubkey>().unwrap(), false),
-            AccountMeta::new(user_pda, false),
-        ],
+    let instructions = vec![instruction::add_bot(
+        &program_id,
+        &wallet_pubkey,
+        bot,
+        limit("max_games"),
+        limit("max_type_price"),
+        limit("daily_volume"),
     )];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
@@ -1,16 +1,16 @@
-use crate::consts::{PROGRAM_ID, RENT};
-use crate::structs::BettingInstruction;
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::pda::find_supported_token_address;
 use clap::ArgMatches;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{read_keypair_file, Signer};
 #[allow(unused_imports)]
 use solana_sdk::signer::keypair::Keypair;
 #[allow(unused_imports)]
 use solana_sdk::signer::signers::Signers;
-use solana_sdk::system_program;
 use solana_sdk::transaction::Transaction;
 
 pub fn add_supported_token(matches: &ArgMatches) {
@@ -32,30 +32,22 @@ pub fn add_supported_token(matches: &ArgMatches) {
         .parse::<Pubkey>()
         .unwrap();
 
-    let (supported_token_data, _) =
-        Pubkey::find_program_address(&["whitelist".as_bytes(), &token.to_bytes()], &program_id);
+    let (supported_token_data, _) = find_supported_token_address(&program_id, &token);
 
     println!("Whitelist {:?}", supported_token_data);
 
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::AddSupportedToken {
-            supported_token: token,
-            feed: "8QQSUPtdRTboa4bKyMftVNRfGFsB4Vp9d7r39hGKi53e"
-                .parse::<Pubkey>()
-                .unwrap(),
-            is_stablecoin: true,
-        },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new_readonly(RENT.parse::<Pubkey>().unwrap(), false),
-            AccountMeta::new(supported_token_data, false),
-        ],
+    let instructions = vec![instruction::add_supported_token(
+        &program_id,
+        &wallet_pubkey,
+        token,
+        "8QQSUPtdRTboa4bKyMftVNRfGFsB4Vp9d7r39hGKi53e"
+            .parse::<Pubkey>()
+            .unwrap(),
+        true,
     )];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
@@ -0,0 +1,61 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn ban_user(matches: &ArgMatches) {
+    let user = matches.value_of("user").unwrap().parse::<Pubkey>().unwrap();
+
+    let reason = matches
+        .value_of("reason")
+        .map(|reason| reason.parse::<u8>().unwrap())
+        .unwrap_or(0);
+
+    let expiry = matches
+        .value_of("expiry")
+        .map(|expiry| expiry.parse::<u64>().unwrap())
+        .unwrap_or(0);
+
+    send(matches, |program_id, payer| {
+        instruction::ban_user(program_id, payer, user, reason, expiry)
+    });
+}
+
+pub fn unban_user(matches: &ArgMatches) {
+    let user = matches.value_of("user").unwrap().parse::<Pubkey>().unwrap();
+
+    send(matches, |program_id, payer| {
+        instruction::unban_user(program_id, payer, user)
+    });
+}
+
+fn send(matches: &ArgMatches, build: impl FnOnce(&Pubkey, &Pubkey) -> Instruction) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let instructions = vec![build(&program_id, &wallet_pubkey)];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
@@ -0,0 +1,87 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::pda::{find_bot_info_address, find_bot_registry_address};
+use betting::state::helpers::{get_bot_info, get_bot_registry_info};
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn remove_bot(matches: &ArgMatches) {
+    let bot = matches.value_of("bot").unwrap().parse::<Pubkey>().unwrap();
+
+    send(matches, |program_id, payer| {
+        instruction::remove_bot(program_id, payer, bot)
+    });
+}
+
+pub fn suspend_bot(matches: &ArgMatches) {
+    let bot = matches.value_of("bot").unwrap().parse::<Pubkey>().unwrap();
+
+    let suspended = matches
+        .value_of("suspended")
+        .unwrap()
+        .parse::<bool>()
+        .unwrap();
+
+    send(matches, |program_id, payer| {
+        instruction::suspend_bot(program_id, payer, bot, suspended)
+    });
+}
+
+pub fn bots(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let (registry_pda, _) = find_bot_registry_address(&program_id);
+
+    let data = client
+        .get_account_data(&registry_pda)
+        .expect("No bots registered yet");
+    let registry = get_bot_registry_info(&data).expect("Can't deserialize bot registry");
+
+    for bot in registry.bots {
+        let (bot_info_pda, _) = find_bot_info_address(&program_id, &bot);
+
+        let data = client
+            .get_account_data(&bot_info_pda)
+            .expect("Can't get bot info");
+        let bot_info = get_bot_info(&data).expect("Can't deserialize bot info");
+
+        println!("{:?}", bot_info);
+    }
+}
+
+fn send(matches: &ArgMatches, build: impl FnOnce(&Pubkey, &Pubkey) -> Instruction) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let instructions = vec![build(&program_id, &wallet_pubkey)];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
@@ -0,0 +1,62 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::pda::{find_betting_address, find_referral_reward_address};
+use betting::state::helpers::get_referral_reward_info;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn claim_referral_rewards(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let token = matches
+        .value_of("token")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+
+    let (betting_pda, _) = find_betting_address(&program_id);
+
+    println!("Betting {:?}", betting_pda);
+
+    let (reward_pda, _) = find_referral_reward_address(&program_id, &wallet_pubkey, &token);
+
+    println!("Reward {:?}", reward_pda);
+
+    let data = client
+        .get_account_data(&reward_pda)
+        .expect("No referral rewards for this token yet");
+    let reward = get_referral_reward_info(&data).expect("Can't deserialize reward");
+
+    println!("Claimable: {}", reward.balance);
+    println!("Claimed before: {}", reward.claimed);
+
+    let instructions = vec![instruction::claim_referral_rewards(
+        &program_id,
+        &wallet_pubkey,
+        &token,
+    )];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
@@ -0,0 +1,105 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::pda::find_betting_address;
+use betting::state::helpers::get_betting_info;
+use betting::state::structs::Config;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+/// Every value not passed on the command line keeps its current on-chain setting.
+pub fn queue_config_change(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let (betting_pda, _) = find_betting_address(&program_id);
+
+    let data = client
+        .get_account_data(&betting_pda)
+        .expect("Betting is not initialized");
+    let betting_info = get_betting_info(&data).expect("Can't deserialize betting");
+
+    let value = |name: &str, current: u64| {
+        matches
+            .value_of(name)
+            .map(|value| value.parse::<u64>().unwrap())
+            .unwrap_or(current)
+    };
+
+    let mut referral_tiers = betting_info.referral_tiers;
+    if let Some(tiers) = matches.value_of("tiers") {
+        referral_tiers = Default::default();
+        for (tier, value) in referral_tiers.iter_mut().zip(tiers.split(',')) {
+            *tier = value.trim().parse::<u64>().unwrap();
+        }
+    }
+
+    let config = Config {
+        referrer_fee: value("winner_fee", betting_info.referrer_fee),
+        admin_fee: value("admin_fee", betting_info.admin_fee),
+        global_fee: value("global_fee", betting_info.global_fee),
+        transaction_fee: value("transaction_fee", betting_info.transaction_fee),
+        close_delay: value("close_delay", betting_info.close_delay),
+        referral_tiers,
+        config_delay: value("config_delay", betting_info.config_delay),
+    };
+
+    println!("Queued config {:?}", config);
+
+    send(matches, &client, |program_id, payer| {
+        instruction::queue_config_change(program_id, payer, config)
+    });
+}
+
+pub fn execute_config_change(matches: &ArgMatches) {
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    send(matches, &client, instruction::execute_config_change);
+}
+
+pub fn cancel_config_change(matches: &ArgMatches) {
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    send(matches, &client, instruction::cancel_config_change);
+}
+
+fn send(
+    matches: &ArgMatches,
+    client: &RpcClient,
+    build: impl FnOnce(&Pubkey, &Pubkey) -> Instruction,
+) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let instructions = vec![build(&program_id, &wallet_pubkey)];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(client, &tx);
+    println!("tx id: {:?}", id);
+}
@@ -0,0 +1,75 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::state::helpers::get_game_info;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn emergency_shutdown(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let shutdown = matches
+        .value_of("shutdown")
+        .unwrap()
+        .parse::<bool>()
+        .unwrap();
+
+    let instructions = vec![instruction::emergency_shutdown(
+        &program_id,
+        &wallet_pubkey,
+        shutdown,
+    )];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+pub fn emergency_refund(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let game_data = matches.value_of("game").unwrap().parse::<Pubkey>().unwrap();
+
+    let data = client.get_account_data(&game_data).expect("Can't get game");
+    let game = get_game_info(&data).expect("Can't deserialize game");
+
+    let instructions = vec![instruction::emergency_refund(
+        &program_id,
+        &wallet_pubkey,
+        &game,
+    )];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
@@ -0,0 +1,70 @@
+use crate::consts::PROGRAM_ID;
+use betting::events::BettingEvent;
+use borsh::BorshDeserialize;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+
+/// Picks our events out of transaction logs. Only `Program data:` lines logged while the
+/// betting program itself is on top of the invoke stack are decoded, so data logged by
+/// other programs it calls into is skipped.
+pub fn decode_events(logs: &[String]) -> Vec<BettingEvent> {
+    let invoke = format!("Program {} invoke", PROGRAM_ID);
+
+    let mut stack: Vec<bool> = vec![];
+    let mut events = vec![];
+
+    for log in logs {
+        if log.starts_with("Program ") && log.contains(" invoke [") {
+            stack.push(log.starts_with(&invoke));
+        } else if log.starts_with("Program ")
+            && (log.ends_with(" success") || log.contains(" failed: "))
+        {
+            stack.pop();
+        } else if let Some(data) = log.strip_prefix("Program data: ") {
+            if stack.last() != Some(&true) {
+                continue;
+            }
+
+            for field in data.split(' ') {
+                if let Some(event) = base64::decode(field)
+                    .ok()
+                    .and_then(|bytes| BettingEvent::try_from_slice(&bytes).ok())
+                {
+                    events.push(event);
+                }
+            }
+        }
+    }
+
+    events
+}
+
+pub fn events(matches: &ArgMatches) {
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let signature = matches
+        .value_of("transaction")
+        .unwrap()
+        .parse::<Signature>()
+        .unwrap();
+
+    let transaction = client
+        .get_transaction(&signature, UiTransactionEncoding::Json)
+        .expect("Transaction not found");
+
+    let logs: Option<Vec<String>> = transaction
+        .transaction
+        .meta
+        .and_then(|meta| meta.log_messages.into());
+
+    for event in decode_events(&logs.unwrap_or_default()) {
+        println!("{:?}", event);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::pda::{
+    find_betting_address, find_bot_info_address, find_bot_registry_address, find_config_address,
+    find_game_address, find_referral_code_address, find_referral_reward_address, find_role_address,
+    find_session_address, find_stats_address, find_supported_token_address,
+    find_type_price_address, find_user_address,
+};
+use betting::state::structs::AccountType;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn migrate_account(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let t = matches
+        .value_of("type")
+        .map(|t| t.parse::<u64>().unwrap())
+        .unwrap_or(0);
+
+    let (account_type, (account, _)) = match matches.value_of("kind").unwrap() {
+        "betting" => (AccountType::BettingInfo, find_betting_address(&program_id)),
+        "supported_token" => (
+            AccountType::SupportedToken,
+            find_supported_token_address(&program_id, &address(matches)),
+        ),
+        "user" => (
+            AccountType::User,
+            find_user_address(&program_id, &address(matches)),
+        ),
+        "game" => (
+            AccountType::Game,
+            find_game_address(&program_id, &address(matches)),
+        ),
+        "type_price" => (
+            AccountType::TypePrice,
+            find_type_price_address(&program_id, t),
+        ),
+        "token_stats" => (
+            AccountType::TokenStats,
+            find_stats_address(&program_id, &address(matches)),
+        ),
+        "referral_code" => (
+            AccountType::ReferralCode,
+            find_referral_code_address(&program_id, matches.value_of("code").unwrap()),
+        ),
+        "referral_reward" => (
+            AccountType::ReferralReward,
+            find_referral_reward_address(
+                &program_id,
+                &address(matches),
+                &matches.value_of("mint").unwrap().parse::<Pubkey>().unwrap(),
+            ),
+        ),
+        "role" => (
+            AccountType::Role,
+            find_role_address(&program_id, &address(matches)),
+        ),
+        "pending_config" => (AccountType::PendingConfig, find_config_address(&program_id)),
+        "bot_info" => (
+            AccountType::BotInfo,
+            find_bot_info_address(&program_id, &address(matches)),
+        ),
+        "bot_registry" => (
+            AccountType::BotRegistry,
+            find_bot_registry_address(&program_id),
+        ),
+        "session" => (
+            AccountType::Session,
+            find_session_address(&program_id, &address(matches)),
+        ),
+        kind => panic!("Unknown account kind {}", kind),
+    };
+
+    println!("Account {:?}", account);
+
+    let instructions = vec![instruction::migrate_account(
+        &program_id,
+        &wallet_pubkey,
+        &account,
+        account_type,
+        t,
+    )];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+/// The wallet or mint the account is derived from.
+fn address(matches: &ArgMatches) -> Pubkey {
+    matches
+        .value_of("address")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap()
+}
@@ -1,10 +1,24 @@
 pub mod add_bot;
 pub mod add_supported_token;
-pub mod close_game;
+pub mod ban;
+pub mod bots;
+pub mod claim_referral_rewards;
+pub mod config_change;
+pub mod emergency;
+pub mod events;
 pub mod forced_close;
 pub mod init;
 pub mod join_game;
 pub mod manually_close;
+pub mod migrate_account;
 pub mod new_game;
+pub mod pause;
+pub mod profile;
+pub mod referral_codes;
 pub mod registration;
+pub mod roles;
+pub mod sessions;
 pub mod setters;
+pub mod settle_game;
+pub mod token_stats;
+pub mod user_stats;
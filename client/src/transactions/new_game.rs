@@ -1,16 +1,16 @@
-use crate::consts::{PROGRAM_ID, RENT};
-use crate::structs::BettingInstruction;
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::pda::find_game_address;
 use clap::ArgMatches;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{read_keypair_file, Signer};
 #[allow(unused_imports)]
 use solana_sdk::signer::keypair::Keypair;
 #[allow(unused_imports)]
 use solana_sdk::signer::signers::Signers;
-use solana_sdk::system_program;
 use solana_sdk::transaction::Transaction;
 
 pub fn new_game(matches: &ArgMatches) {
@@ -26,121 +26,51 @@ pub fn new_game(matches: &ArgMatches) {
     let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
     let wallet_pubkey = wallet_keypair.pubkey();
 
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    println!("Betting {:?}", betting_pda);
-
     let t = matches.value_of("value").unwrap().parse::<u64>().unwrap();
 
-    let (supported_token_data, _) = Pubkey::find_program_address(
-        &[
-            "whitelist".as_bytes(),
-            &"3e7FKiXHn1kmMSTLDgJkMWxwd2WA6PM9niYcxbfk8EKN"
-                .parse::<Pubkey>()
-                .unwrap()
-                .to_bytes(),
-        ],
-        &program_id,
-    );
-
-    let (user_data, _) = Pubkey::find_program_address(
-        &[
-            "user".as_bytes(),
-            &"4kMtMnYWFbsMc7M3jcdnfCceHaiXmrqaMz2QZQAmn88i"
-                .parse::<Pubkey>()
-                .unwrap()
-                .to_bytes(),
-        ],
-        &program_id,
-    );
+    // with -u the wallet is the session delegate of that user
+    let user = matches
+        .value_of("user")
+        .map(|user| user.parse::<Pubkey>().unwrap());
 
-    let (game_data, _) = Pubkey::find_program_address(
-        &[
-            "game".as_bytes(),
-            &"4kMtMnYWFbsMc7M3jcdnfCceHaiXmrqaMz2QZQAmn88i"
-                .parse::<Pubkey>()
-                .unwrap()
-                .to_bytes(),
-        ],
-        &program_id,
-    );
-
-    println!("Whitelist {:?}", supported_token_data);
-
-    println!("User {:?}", user_data);
+    let (game_data, _) = find_game_address(&program_id, &user.unwrap_or(wallet_pubkey));
 
     println!("Game {:?}", game_data);
 
-    let source = spl_associated_token_account::get_associated_token_address(
-        &wallet_pubkey,
-        &"3e7FKiXHn1kmMSTLDgJkMWxwd2WA6PM9niYcxbfk8EKN"
-            .parse::<Pubkey>()
-            .unwrap(),
-    );
-
-    let destination = spl_associated_token_account::get_associated_token_address(
-        &game_data,
-        &"3e7FKiXHn1kmMSTLDgJkMWxwd2WA6PM9niYcxbfk8EKN"
-            .parse::<Pubkey>()
-            .unwrap(),
-    );
-
-    println!("Source {:?}", source);
-
-    println!("Destination {:?}", destination);
-
-    let (type_price_pda, _) = Pubkey::find_program_address(
-        &["type_price".as_bytes(), t.to_string().as_bytes()],
-        &program_id,
-    );
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::NewGame {
+    let token = "3e7FKiXHn1kmMSTLDgJkMWxwd2WA6PM9niYcxbfk8EKN"
+        .parse::<Pubkey>()
+        .unwrap();
+    let chainlink_program = "HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny"
+        .parse::<Pubkey>()
+        .unwrap();
+    let feed = "99B2bTijsU6f1GCT73HmdR7HCFFjGMBcPZY6jZ96ynrR"
+        .parse::<Pubkey>()
+        .unwrap();
+
+    let instructions = vec![match user {
+        Some(user) => instruction::session_new_game(
+            &program_id,
+            &wallet_pubkey,
+            user,
+            &token,
+            &chainlink_program,
+            &feed,
+            t,
+            false,
+        ),
+        None => instruction::new_game(
+            &program_id,
+            &wallet_pubkey,
+            &token,
+            &chainlink_program,
+            &feed,
             t,
-            support_bot: false,
-        },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-            AccountMeta::new_readonly(RENT.parse::<Pubkey>().unwrap(), false),
-            AccountMeta::new(supported_token_data, false),
-            AccountMeta::new(user_data, false),
-            AccountMeta::new(game_data, false),
-            AccountMeta::new_readonly(
-                "HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny"
-                    .parse::<Pubkey>()
-                    .unwrap(),
-                false,
-            ),
-            AccountMeta::new_readonly(
-                "99B2bTijsU6f1GCT73HmdR7HCFFjGMBcPZY6jZ96ynrR"
-                    .parse::<Pubkey>()
-                    .unwrap(),
-                false,
-            ),
-            AccountMeta::new(source, false),
-            AccountMeta::new(destination, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(
-                "3e7FKiXHn1kmMSTLDgJkMWxwd2WA6PM9niYcxbfk8EKN"
-                    .parse::<Pubkey>()
-                    .unwrap(),
-                false,
-            ),
-            AccountMeta::new_readonly(
-                "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-                    .parse::<Pubkey>()
-                    .unwrap(),
-                false,
-            ),
-            AccountMeta::new(type_price_pda, false),
-        ],
-    )];
+            false,
+        ),
+    }];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
@@ -0,0 +1,84 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::consts::{PAUSE_JOIN_GAME, PAUSE_NEW_GAME, PAUSE_SETTLE};
+use betting::instruction;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn set_pause_flags(matches: &ArgMatches) {
+    let flags = parse_actions(matches.value_of("actions").unwrap_or(""));
+
+    send(matches, |program_id, payer| {
+        instruction::set_pause_flags(program_id, payer, flags)
+    });
+}
+
+pub fn set_token_paused(matches: &ArgMatches) {
+    let supported_token = matches
+        .value_of("token")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+
+    let paused = matches.value_of("paused").unwrap().parse::<bool>().unwrap();
+
+    send(matches, |program_id, payer| {
+        instruction::set_token_paused(program_id, payer, supported_token, paused)
+    });
+}
+
+pub fn set_type_paused(matches: &ArgMatches) {
+    let t = matches.value_of("type").unwrap().parse::<u64>().unwrap();
+
+    let paused = matches.value_of("paused").unwrap().parse::<bool>().unwrap();
+
+    send(matches, |program_id, payer| {
+        instruction::set_type_paused(program_id, payer, t, paused)
+    });
+}
+
+/// Turns a comma separated list like "new_game,join_game" into the on-chain pause bitset.
+fn parse_actions(actions: &str) -> u8 {
+    actions
+        .split(',')
+        .filter(|action| !action.trim().is_empty())
+        .fold(0, |flags, action| {
+            flags
+                | match action.trim() {
+                    "new_game" => PAUSE_NEW_GAME,
+                    "join_game" => PAUSE_JOIN_GAME,
+                    "settle" => PAUSE_SETTLE,
+                    _ => panic!("Unknown action {}", action),
+                }
+        })
+}
+
+fn send(matches: &ArgMatches, build: impl FnOnce(&Pubkey, &Pubkey) -> Instruction) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let instructions = vec![build(&program_id, &wallet_pubkey)];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
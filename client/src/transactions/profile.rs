@@ -0,0 +1,188 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction::{self, Referrer};
+use betting::pda::find_user_address;
+use betting::processor::profile;
+use betting::state::helpers::get_user_info;
+use betting::state::structs::Limits;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+/// The salted hash of -p/--password, if one is given. The salt (-k) is kept by the user and
+/// never sent to the program.
+pub fn credential_hash_of(matches: &ArgMatches, wallet: &Pubkey) -> Option<[u8; 32]> {
+    matches.value_of("password").map(|password| {
+        let salt = matches.value_of("salt").unwrap();
+        profile::credential_hash(wallet, salt.as_bytes(), password)
+    })
+}
+
+/// Without -p the stored hash is removed.
+pub fn set_credential_hash(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let credential_hash = credential_hash_of(matches, &wallet_pubkey);
+
+    let instructions = vec![instruction::set_credential_hash(
+        &program_id,
+        &wallet_pubkey,
+        credential_hash,
+    )];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+pub fn update_profile(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let nickname = matches.value_of("nickname").unwrap_or_default();
+
+    let avatar_uri = matches.value_of("avatar").unwrap_or_default();
+
+    let instructions = vec![instruction::update_profile(
+        &program_id,
+        &wallet_pubkey,
+        nickname.to_string(),
+        avatar_uri.to_string(),
+    )];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+/// -r a wallet or -c a referral code, as in registration.
+pub fn change_referrer(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let referrer = match matches.value_of("code") {
+        Some(code) => Referrer::Code(code.to_string()),
+        None => Referrer::Wallet(
+            matches
+                .value_of("referrer")
+                .unwrap()
+                .parse::<Pubkey>()
+                .unwrap(),
+        ),
+    };
+
+    let instructions = vec![instruction::change_referrer(
+        &program_id,
+        &wallet_pubkey,
+        referrer,
+    )];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+pub fn deregister(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let instructions = vec![instruction::deregister(&program_id, &wallet_pubkey)];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+/// Every limit not passed on the command line keeps its current value; 0 removes a limit.
+/// Loosened limits only apply after the cooling-off period.
+pub fn set_limits(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let (user_pda, _) = find_user_address(&program_id, &wallet_pubkey);
+
+    let data = client
+        .get_account_data(&user_pda)
+        .expect("User is not registered");
+    let user_info = get_user_info(&data).expect("Can't deserialize user");
+
+    let value = |name: &str, current: u64| {
+        matches
+            .value_of(name)
+            .map(|value| value.parse::<u64>().unwrap())
+            .unwrap_or(current)
+    };
+
+    let limits = Limits {
+        max_type_price: value("max_type_price", user_info.limits.max_type_price),
+        max_daily_games: value("max_daily_games", user_info.limits.max_daily_games),
+        daily_loss_limit: value("daily_loss_limit", user_info.limits.daily_loss_limit),
+        excluded_until: value("excluded_until", user_info.limits.excluded_until),
+    };
+
+    println!("Limits {:?}", limits);
+
+    let instructions = vec![instruction::set_limits(&program_id, &wallet_pubkey, limits)];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
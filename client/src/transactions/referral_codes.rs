@@ -0,0 +1,95 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::pda::find_referral_code_address;
+use betting::state::helpers::get_referral_code_info;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn claim_referral_code(matches: &ArgMatches) {
+    let code = matches.value_of("code").unwrap().to_string();
+
+    send(matches, &code, |program_id, payer| {
+        instruction::claim_referral_code(program_id, payer, code.clone())
+    });
+}
+
+pub fn transfer_referral_code(matches: &ArgMatches) {
+    let code = matches.value_of("code").unwrap().to_string();
+
+    let new_owner = matches
+        .value_of("owner")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+
+    send(matches, &code, |program_id, payer| {
+        instruction::transfer_referral_code(program_id, payer, code.clone(), new_owner)
+    });
+}
+
+pub fn release_referral_code(matches: &ArgMatches) {
+    let code = matches.value_of("code").unwrap().to_string();
+
+    send(matches, &code, |program_id, payer| {
+        instruction::release_referral_code(program_id, payer, code.clone())
+    });
+}
+
+pub fn referral_code(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let code = matches.value_of("code").unwrap();
+
+    let (code_pda, _) = find_referral_code_address(&program_id, code);
+
+    println!("Referral code {:?}", code_pda);
+
+    let data = client
+        .get_account_data(&code_pda)
+        .expect("Referral code is not claimed");
+    let referral_code = get_referral_code_info(&data).expect("Can't deserialize referral code");
+
+    println!("Code: {}", referral_code.code);
+    println!("Owner: {:?}", referral_code.owner);
+}
+
+fn send(matches: &ArgMatches, code: &str, build: impl FnOnce(&Pubkey, &Pubkey) -> Instruction) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let (code_pda, _) = find_referral_code_address(&program_id, code);
+
+    println!("Referral code {:?}", code_pda);
+
+    let instructions = vec![build(&program_id, &wallet_pubkey)];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
@@ -1,16 +1,16 @@
-use crate::consts::{PROGRAM_ID, RENT};
-use crate::structs::BettingInstruction;
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use crate::transactions::profile::credential_hash_of;
+use betting::instruction::{self, Referrer};
 use clap::ArgMatches;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{read_keypair_file, Signer};
 #[allow(unused_imports)]
 use solana_sdk::signer::keypair::Keypair;
 #[allow(unused_imports)]
 use solana_sdk::signer::signers::Signers;
-use solana_sdk::system_program;
 use solana_sdk::transaction::Transaction;
 
 pub fn registration(matches: &ArgMatches) {
@@ -26,37 +26,28 @@ pub fn registration(matches: &ArgMatches) {
     let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
     let wallet_pubkey = wallet_keypair.pubkey();
 
-    let user = "4kMtMnYWFbsMc7M3jcdnfCceHaiXmrqaMz2QZQAmn88i"
-        .parse::<Pubkey>()
-        .unwrap();
-
-    let referrer = matches
-        .value_of("referrer")
-        .unwrap()
-        .parse::<Pubkey>()
-        .unwrap();
-
-    let password = matches.value_of("password").unwrap();
+    let referrer = match matches.value_of("code") {
+        Some(code) => Referrer::Code(code.to_string()),
+        None => Referrer::Wallet(
+            matches
+                .value_of("referrer")
+                .unwrap()
+                .parse::<Pubkey>()
+                .unwrap(),
+        ),
+    };
 
-    let (user_pda, _) =
-        Pubkey::find_program_address(&["user".as_bytes(), &user.to_bytes()], &program_id);
+    let credential_hash = credential_hash_of(matches, &wallet_pubkey);
 
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::Registration {
-            referrer,
-            password: password.to_string(),
-        },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new_readonly(RENT.parse::<Pubkey>().unwrap(), false),
-            AccountMeta::new(user_pda, false),
-        ],
+    let instructions = vec![instruction::registration(
+        &program_id,
+        &wallet_pubkey,
+        referrer,
+        credential_hash,
     )];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
@@ -0,0 +1,88 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::consts::{BOT_MANAGER, FEE_ADMIN, PAUSER, RESOLVER, TOKEN_LISTER};
+use betting::instruction;
+use betting::pda::find_role_address;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn grant_role(matches: &ArgMatches) {
+    let address = matches
+        .value_of("address")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+
+    let permissions = parse_roles(matches.value_of("roles").unwrap());
+
+    send(matches, &address, |program_id, payer| {
+        instruction::grant_role(program_id, payer, address, permissions)
+    });
+}
+
+pub fn revoke_role(matches: &ArgMatches) {
+    let address = matches
+        .value_of("address")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+
+    let permissions = parse_roles(matches.value_of("roles").unwrap());
+
+    send(matches, &address, |program_id, payer| {
+        instruction::revoke_role(program_id, payer, address, permissions)
+    });
+}
+
+/// Turns a comma separated list like "fee_admin,pauser" into the on-chain permission bitset.
+fn parse_roles(roles: &str) -> u8 {
+    roles.split(',').fold(0, |permissions, role| {
+        permissions
+            | match role.trim() {
+                "fee_admin" => FEE_ADMIN,
+                "pauser" => PAUSER,
+                "resolver" => RESOLVER,
+                "token_lister" => TOKEN_LISTER,
+                "bot_manager" => BOT_MANAGER,
+                _ => panic!("Unknown role {}", role),
+            }
+    })
+}
+
+fn send(
+    matches: &ArgMatches,
+    address: &Pubkey,
+    build: impl FnOnce(&Pubkey, &Pubkey) -> Instruction,
+) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let (role_pda, _) = find_role_address(&program_id, address);
+
+    println!("Role {:?}", role_pda);
+
+    let instructions = vec![build(&program_id, &wallet_pubkey)];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
@@ -0,0 +1,120 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
+use betting::pda::find_betting_address;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+use spl_associated_token_account::get_associated_token_address;
+
+/// Approves the betting PDA to spend up to -a of token -t and opens the session in the same
+/// transaction. -a is a token amount, -p and -m are type prices.
+pub fn create_session(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let delegate = matches
+        .value_of("delegate")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+    let expires_at = matches
+        .value_of("expires_at")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let value = |name: &str| {
+        matches
+            .value_of(name)
+            .map(|value| value.parse::<u64>().unwrap())
+            .unwrap_or(0)
+    };
+    let token = matches
+        .value_of("token")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+    let allowance = matches
+        .value_of("allowance")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+
+    let instructions = vec![
+        spl_token::instruction::approve(
+            &spl_token::id(),
+            &get_associated_token_address(&wallet_pubkey, &token),
+            &find_betting_address(&program_id).0,
+            &wallet_pubkey,
+            &[],
+            allowance,
+        )
+        .unwrap(),
+        instruction::create_session(
+            &program_id,
+            &wallet_pubkey,
+            delegate,
+            expires_at,
+            value("max_stake_per_game"),
+            value("max_total"),
+        ),
+    ];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+/// Closes the session and, with -t, revokes the approval on that token account as well.
+pub fn revoke_session(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let mut instructions = vec![instruction::revoke_session(&program_id, &wallet_pubkey)];
+
+    if let Some(token) = matches.value_of("token") {
+        let token = token.parse::<Pubkey>().unwrap();
+
+        instructions.push(
+            spl_token::instruction::revoke(
+                &spl_token::id(),
+                &get_associated_token_address(&wallet_pubkey, &token),
+                &wallet_pubkey,
+                &[],
+            )
+            .unwrap(),
+        );
+    }
+
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
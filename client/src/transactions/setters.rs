@@ -1,55 +1,17 @@
-use crate::consts::{PROGRAM_ID, RENT};
-use crate::structs::BettingInstruction;
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::instruction;
 use clap::ArgMatches;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
-use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{read_keypair_file, Signer};
 #[allow(unused_imports)]
 use solana_sdk::signer::keypair::Keypair;
 #[allow(unused_imports)]
 use solana_sdk::signer::signers::Signers;
-use solana_sdk::system_program;
 use solana_sdk::transaction::Transaction;
 
-pub fn new_delay(matches: &ArgMatches) {
-    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
-
-    let url = match matches.value_of("env") {
-        Some("dev") => "https://api.testnet.solana.com",
-        _ => "https://api.mainnet-beta.solana.com",
-    };
-    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
-
-    let wallet_path = matches.value_of("sign").unwrap();
-    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
-    let wallet_pubkey = wallet_keypair.pubkey();
-
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    let new_delay = matches
-        .value_of("new_delay")
-        .unwrap()
-        .parse::<u64>()
-        .unwrap();
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::ChangeCloseDelay { new_delay },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-        ],
-    )];
-    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
-    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
-    tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
-    println!("tx id: {:?}", id);
-}
-
 pub fn lock_bets(matches: &ArgMatches) {
     let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
 
@@ -63,21 +25,11 @@ pub fn lock_bets(matches: &ArgMatches) {
     let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
     let wallet_pubkey = wallet_keypair.pubkey();
 
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::LockBets,
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-        ],
-    )];
+    let instructions = vec![instruction::lock_bets(&program_id, &wallet_pubkey)];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
 
@@ -94,99 +46,19 @@ pub fn unlock_bets(matches: &ArgMatches) {
     let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
     let wallet_pubkey = wallet_keypair.pubkey();
 
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::UnlockBets,
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-        ],
-    )];
-    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
-    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
-    tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
-    println!("tx id: {:?}", id);
-}
-
-pub fn new_manager(matches: &ArgMatches) {
-    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
-
-    let url = match matches.value_of("env") {
-        Some("dev") => "https://api.testnet.solana.com",
-        _ => "https://api.mainnet-beta.solana.com",
-    };
-    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
-
-    let wallet_path = matches.value_of("sign").unwrap();
-    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
-    let wallet_pubkey = wallet_keypair.pubkey();
-
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    let manager = matches
-        .value_of("manager")
-        .unwrap()
-        .parse::<Pubkey>()
-        .unwrap();
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::NewManager { manager },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-        ],
-    )];
+    let instructions = vec![instruction::unlock_bets(&program_id, &wallet_pubkey)];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
 
-pub fn set_global_fee(matches: &ArgMatches) {
-    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
-
-    let url = match matches.value_of("env") {
-        Some("dev") => "https://api.testnet.solana.com",
-        _ => "https://api.mainnet-beta.solana.com",
-    };
-    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
-
-    let wallet_path = matches.value_of("sign").unwrap();
-    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
-    let wallet_pubkey = wallet_keypair.pubkey();
-
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    let fee = matches.value_of("fee").unwrap().parse::<u64>().unwrap();
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::SetGlobalFee { fee },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-        ],
-    )];
-    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
-    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
-    tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
-    println!("tx id: {:?}", id);
-}
-
-pub fn set_admin_fee(matches: &ArgMatches) {
+pub fn set_type_price(matches: &ArgMatches) {
     let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
 
     let url = match matches.value_of("env") {
-        Some("dev") => "https://api.testnet.solana.com",
+        Some("dev") => "https://api.devnet.solana.com",
         _ => "https://api.mainnet-beta.solana.com",
     };
     let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
@@ -195,27 +67,24 @@ pub fn set_admin_fee(matches: &ArgMatches) {
     let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
     let wallet_pubkey = wallet_keypair.pubkey();
 
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
+    let t = matches.value_of("type").unwrap().parse::<u64>().unwrap();
 
-    let fee = matches.value_of("fee").unwrap().parse::<u64>().unwrap();
+    let price = matches.value_of("price").unwrap().parse::<u64>().unwrap();
 
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::SetAdminFee { fee },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-        ],
+    let instructions = vec![instruction::set_type_price(
+        &program_id,
+        &wallet_pubkey,
+        t,
+        price,
     )];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
 
-pub fn set_transaction_fee(matches: &ArgMatches) {
+pub fn propose_admin(matches: &ArgMatches) {
     let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
 
     let url = match matches.value_of("env") {
@@ -228,69 +97,25 @@ pub fn set_transaction_fee(matches: &ArgMatches) {
     let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
     let wallet_pubkey = wallet_keypair.pubkey();
 
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    let fee = matches.value_of("fee").unwrap().parse::<u64>().unwrap();
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::SetTransactionFee { fee },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-        ],
-    )];
-    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
-    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
-    tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
-    println!("tx id: {:?}", id);
-}
-
-pub fn set_type_price(matches: &ArgMatches) {
-    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
-
-    let url = match matches.value_of("env") {
-        Some("dev") => "https://api.devnet.solana.com",
-        _ => "https://api.mainnet-beta.solana.com",
-    };
-    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
-
-    let wallet_path = matches.value_of("sign").unwrap();
-    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
-    let wallet_pubkey = wallet_keypair.pubkey();
-
-    let t = matches.value_of("type").unwrap().parse::<u64>().unwrap();
+    let admin = matches
+        .value_of("admin")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
 
-    let (type_price_pda, _) = Pubkey::find_program_address(
-        &["type_price".as_bytes(), t.to_string().as_bytes()],
+    let instructions = vec![instruction::propose_admin(
         &program_id,
-    );
-
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    let price = matches.value_of("price").unwrap().parse::<u64>().unwrap();
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::SetTypePrice { t, price },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new_readonly(RENT.parse::<Pubkey>().unwrap(), false),
-            AccountMeta::new(betting_pda, false),
-            AccountMeta::new(type_price_pda, false),
-        ],
+        &wallet_pubkey,
+        admin,
     )];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
 
-pub fn set_winner_fee(matches: &ArgMatches) {
+pub fn accept_admin(matches: &ArgMatches) {
     let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
 
     let url = match matches.value_of("env") {
@@ -303,22 +128,10 @@ pub fn set_winner_fee(matches: &ArgMatches) {
     let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
     let wallet_pubkey = wallet_keypair.pubkey();
 
-    let (betting_pda, _) = Pubkey::find_program_address(&["betting".as_bytes()], &program_id);
-
-    let fee = matches.value_of("fee").unwrap().parse::<u64>().unwrap();
-
-    let instructions = vec![Instruction::new_with_borsh(
-        program_id,
-        &BettingInstruction::SetWinnerFee { fee },
-        vec![
-            AccountMeta::new(wallet_pubkey, true),
-            AccountMeta::new(system_program::id(), false),
-            AccountMeta::new(betting_pda, false),
-        ],
-    )];
+    let instructions = vec![instruction::accept_admin(&program_id, &wallet_pubkey)];
     let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
     let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
     tx.sign(&vec![&wallet_keypair], recent_blockhash);
-    let id = client.send_transaction(&tx).expect("Transaction failed.");
+    let id = send_transaction(&client, &tx);
     println!("tx id: {:?}", id);
 }
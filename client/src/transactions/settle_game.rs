@@ -0,0 +1,170 @@
+use crate::consts::PROGRAM_ID;
+use crate::error::send_transaction;
+use betting::consts::{
+    MAX_REFERRAL_DEPTH, PAYOUT_ADMIN, PAYOUT_REFERRERS, PAYOUT_WINNER, WINNER_GAMER1,
+};
+use betting::instruction;
+use betting::pda::{find_betting_address, find_game_address, find_user_address};
+use betting::state::helpers::{get_betting_info, get_game_info, get_user_info};
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+#[allow(unused_imports)]
+use solana_sdk::signer::keypair::Keypair;
+#[allow(unused_imports)]
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::Transaction;
+
+pub fn settle_game(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let user = matches.value_of("user").unwrap().parse::<Pubkey>().unwrap();
+
+    let winner = matches
+        .value_of("winner")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+
+    let t = matches.value_of("type").unwrap().parse::<u64>().unwrap();
+
+    let (game_data, _) = find_game_address(&program_id, &user);
+
+    let game = get_game_info(&client.get_account_data(&game_data).expect("No game"))
+        .expect("Can't deserialize game");
+
+    println!("Game {:?}", game_data);
+
+    let instructions = vec![instruction::settle(
+        &program_id,
+        &wallet_pubkey,
+        &game,
+        winner,
+        t,
+    )];
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+/// Pushes one payout of a settled game: -i 0 admin fee, 1 referral rewards,
+/// 2 winner's share of the first stake, 3 the second stake. 1 has to go before 0.
+pub fn distribute(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.testnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let wallet_path = matches.value_of("sign").unwrap();
+    let wallet_keypair = read_keypair_file(wallet_path).expect("Can't open file-wallet");
+    let wallet_pubkey = wallet_keypair.pubkey();
+
+    let user = matches.value_of("user").unwrap().parse::<Pubkey>().unwrap();
+
+    let index = matches.value_of("index").unwrap().parse::<u8>().unwrap();
+
+    let (betting_pda, _) = find_betting_address(&program_id);
+
+    let (game_data, _) = find_game_address(&program_id, &user);
+
+    let betting = get_betting_info(
+        &client
+            .get_account_data(&betting_pda)
+            .expect("Betting is not initialized"),
+    )
+    .expect("Can't deserialize betting");
+
+    let game = get_game_info(&client.get_account_data(&game_data).expect("No game"))
+        .expect("Can't deserialize game");
+
+    let winner = if game.winner == WINNER_GAMER1 {
+        game.gamer1
+    } else {
+        game.gamer2
+    };
+
+    let (recipient, token) = match index {
+        PAYOUT_ADMIN => (betting.admin, game.token1),
+        PAYOUT_REFERRERS => (betting_pda, game.token1),
+        PAYOUT_WINNER => (winner, game.token1),
+        _ => (winner, game.token2),
+    };
+
+    println!("Game {:?}", game_data);
+
+    println!("Recipient {:?}", recipient);
+
+    let instructions = if index == PAYOUT_REFERRERS {
+        let referrers1 = referrers_of(&client, &program_id, &game.gamer1);
+
+        let referrers2 = referrers_of(&client, &program_id, &game.gamer2);
+
+        vec![instruction::distribute_referrers(
+            &program_id,
+            &wallet_pubkey,
+            &game,
+            &referrers1,
+            &referrers2,
+        )]
+    } else {
+        vec![instruction::distribute(
+            &program_id,
+            &wallet_pubkey,
+            user,
+            index,
+            &recipient,
+            &token,
+        )]
+    };
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&wallet_pubkey));
+    let recent_blockhash = client.get_latest_blockhash().expect("Can't get blockhash");
+    tx.sign(&vec![&wallet_keypair], recent_blockhash);
+    let id = send_transaction(&client, &tx);
+    println!("tx id: {:?}", id);
+}
+
+/// The referrers above `player`, read from their User accounts up to the depth the program
+/// pays out.
+fn referrers_of(client: &RpcClient, program_id: &Pubkey, player: &Pubkey) -> Vec<Pubkey> {
+    let mut referrers = vec![];
+    let mut wallet = *player;
+
+    while referrers.len() < MAX_REFERRAL_DEPTH {
+        let (user_pda, _) = find_user_address(program_id, &wallet);
+
+        let referrer = match client.get_account_data(&user_pda) {
+            Ok(data) => {
+                get_user_info(&data)
+                    .expect("Can't deserialize user")
+                    .referrer
+            }
+            Err(_) => break,
+        };
+
+        if referrer == Pubkey::default() {
+            break;
+        }
+
+        referrers.push(referrer);
+        wallet = referrer;
+    }
+
+    referrers
+}
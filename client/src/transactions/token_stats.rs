@@ -0,0 +1,39 @@
+use crate::consts::PROGRAM_ID;
+use betting::pda::find_stats_address;
+use betting::state::helpers::get_token_stats_info;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+pub fn token_stats(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let token = matches
+        .value_of("token")
+        .unwrap()
+        .parse::<Pubkey>()
+        .unwrap();
+
+    let (stats_pda, _) = find_stats_address(&program_id, &token);
+
+    println!("Stats {:?}", stats_pda);
+
+    let data = client
+        .get_account_data(&stats_pda)
+        .expect("No stats for this token yet");
+    let stats = get_token_stats_info(&data).expect("Can't deserialize stats");
+
+    println!("Token: {:?}", stats.mint);
+    println!("Staked volume: {}", stats.staked_volume);
+    println!("Games settled: {}", stats.games_settled);
+    println!("Admin fees: {}", stats.admin_fees);
+    println!("Referrer fees: {}", stats.referrer_fees);
+    println!("Penalties: {}", stats.penalties);
+}
@@ -0,0 +1,52 @@
+use crate::consts::PROGRAM_ID;
+use betting::pda::find_user_address;
+use betting::state::helpers::get_user_info;
+use clap::ArgMatches;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+pub fn user_stats(matches: &ArgMatches) {
+    let program_id = PROGRAM_ID.parse::<Pubkey>().unwrap();
+
+    let url = match matches.value_of("env") {
+        Some("dev") => "https://api.devnet.solana.com",
+        _ => "https://api.mainnet-beta.solana.com",
+    };
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    let user = matches.value_of("user").unwrap().parse::<Pubkey>().unwrap();
+
+    let (user_pda, _) = find_user_address(&program_id, &user);
+
+    println!("User {:?}", user_pda);
+
+    let data = client
+        .get_account_data(&user_pda)
+        .expect("User is not registered");
+    let user_info = get_user_info(&data).expect("Can't deserialize user");
+
+    let win_rate = if user_info.games_played == 0 {
+        0.0
+    } else {
+        user_info.wins as f64 * 100.0 / user_info.games_played as f64
+    };
+
+    println!("Games played: {}", user_info.games_played);
+    println!("Wins: {}", user_info.wins);
+    println!("Losses: {}", user_info.losses);
+    println!("Draws: {}", user_info.draws);
+    println!("Cancellations: {}", user_info.cancellations);
+    println!("Win rate: {:.2}%", win_rate);
+    println!("Net profit: {}", user_info.net_profit);
+    println!("Turnover: {}", user_info.turnover);
+    println!("Last game at: {}", user_info.last_game_at);
+    println!("Limits: {:?}", user_info.limits);
+
+    if user_info.pending_limits_at != 0 {
+        println!(
+            "Pending limits: {:?} from {}",
+            user_info.pending_limits, user_info.pending_limits_at
+        );
+    }
+}
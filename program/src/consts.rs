@@ -12,6 +12,91 @@ pub const GAME: &[u8] = "game".as_bytes();
 
 pub const TYPE_PRICE: &[u8] = "type_price".as_bytes();
 
-pub const ADMIN: &str = "4kMtMnYWFbsMc7M3jcdnfCceHaiXmrqaMz2QZQAmn88i";
+pub const STATS: &[u8] = "stats".as_bytes();
+
+pub const REFERRAL: &[u8] = "referral".as_bytes();
+
+pub const REFERRAL_CODE: &[u8] = "referral_code".as_bytes();
+
+pub const ROLE: &[u8] = "role".as_bytes();
+
+pub const CONFIG: &[u8] = "config".as_bytes();
+
+pub const BOT: &[u8] = "bot".as_bytes();
+
+pub const BOT_REGISTRY: &[u8] = "bot_registry".as_bytes();
+
+pub const SESSION: &[u8] = "session".as_bytes();
 
 pub const PRECISION: u64 = 100000000;
+
+pub const ACCOUNT_VERSION: u8 = 1;
+
+pub const POD_ACCOUNT_VERSION: u8 = 2;
+
+/// `User` accounts since the plaintext password was replaced by a credential hash.
+pub const PROFILE_ACCOUNT_VERSION: u8 = 3;
+
+/// `User` accounts since they keep game statistics.
+pub const STATS_ACCOUNT_VERSION: u8 = 4;
+
+/// `User` accounts since they keep responsible-gaming limits.
+pub const USER_ACCOUNT_VERSION: u8 = 5;
+
+/// Every account since the one-byte `AccountType` tag was replaced by an 8-byte discriminator.
+pub const DISCRIMINATOR_ACCOUNT_VERSION: u8 = 6;
+
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// How long a loosened `SetLimits` waits before it applies.
+pub const LIMITS_COOLING_OFF: Timestamp = SECONDS_IN_THE_DAY;
+
+/// The longest timelock a queued config change may set.
+pub const MAX_CONFIG_DELAY: Timestamp = 30 * SECONDS_IN_THE_DAY;
+
+/// The longest a queued config change may make players wait before closing an unjoined game.
+pub const MAX_CLOSE_DELAY: Timestamp = 7 * SECONDS_IN_THE_DAY;
+
+pub const MAX_PASSWORD_LEN: usize = 32;
+
+pub const MAX_NICKNAME_LEN: usize = 32;
+
+pub const MAX_AVATAR_URI_LEN: usize = 128;
+
+pub const RESERVED_SPACE: usize = 32;
+
+pub const MAX_REFERRAL_DEPTH: usize = 3;
+
+pub const MIN_REFERRAL_CODE_LEN: usize = 3;
+
+pub const MAX_REFERRAL_CODE_LEN: usize = 16;
+
+pub const FEE_ADMIN: u8 = 1 << 0;
+
+pub const PAUSER: u8 = 1 << 1;
+
+pub const RESOLVER: u8 = 1 << 2;
+
+pub const TOKEN_LISTER: u8 = 1 << 3;
+
+pub const BOT_MANAGER: u8 = 1 << 4;
+
+pub const PAUSE_NEW_GAME: u8 = 1 << 0;
+
+pub const PAUSE_JOIN_GAME: u8 = 1 << 1;
+
+pub const PAUSE_SETTLE: u8 = 1 << 2;
+
+pub const WINNER_GAMER1: u8 = 1;
+
+pub const WINNER_GAMER2: u8 = 2;
+
+pub const PAYOUT_ADMIN: u8 = 0;
+
+pub const PAYOUT_REFERRERS: u8 = 1;
+
+pub const PAYOUT_WINNER: u8 = 2;
+
+pub const PAYOUT_WINNER2: u8 = 3;
+
+pub const ALL_PAYOUTS: u8 = 0b1111;
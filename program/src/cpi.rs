@@ -0,0 +1,365 @@
+//! Helpers for calling the program from other on-chain programs.
+//!
+//! The account structs are the ones the processors parse, so their field order is the account
+//! order of the instruction. `signer_seeds` are passed to `invoke_signed` and let a PDA of the
+//! calling program act as the player or the settler; pass `&[]` when the payer signs the outer
+//! transaction itself.
+
+use crate::error::ContractError;
+use crate::instruction::BettingInstruction;
+use crate::processor::require;
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke_signed;
+use solana_program::pubkey::Pubkey;
+
+pub use crate::processor::bet::Accounts as NewGame;
+pub use crate::processor::join_game::Accounts as JoinGame;
+pub use crate::processor::settle_game::{Accounts as Settle, DistributeAccounts as Distribute};
+
+fn payer_meta(info: &AccountInfo) -> AccountMeta {
+    AccountMeta::new(*info.key, true)
+}
+
+fn writable_meta(info: &AccountInfo) -> AccountMeta {
+    AccountMeta::new(*info.key, false)
+}
+
+fn readonly_meta(info: &AccountInfo) -> AccountMeta {
+    AccountMeta::new_readonly(*info.key, false)
+}
+
+fn call<'b>(
+    program: &AccountInfo<'b>,
+    instruction: &BettingInstruction,
+    accounts: Vec<&AccountInfo<'b>>,
+    metas: Vec<AccountMeta>,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let mut infos: Vec<AccountInfo<'b>> = accounts.into_iter().cloned().collect();
+    infos.push(program.clone());
+
+    invoke_signed(
+        &Instruction::new_with_borsh(*program.key, instruction, metas),
+        &infos,
+        signer_seeds,
+    )
+}
+
+/// Opens a game of type `t` with `accounts.payer` as the first gamer.
+pub fn new_game<'b>(
+    program: &AccountInfo<'b>,
+    accounts: &NewGame<'_, 'b>,
+    t: u64,
+    support_bot: bool,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let (infos, metas) = new_game_accounts(accounts);
+
+    call(
+        program,
+        &BettingInstruction::NewGame { t, support_bot },
+        infos,
+        metas,
+        signer_seeds,
+    )
+}
+
+/// Opens a game of type `t` for `user` with `accounts.payer` as their session delegate.
+/// `accounts.bot_info` and `accounts.session` are both required, the session comes last.
+pub fn session_new_game<'b>(
+    program: &AccountInfo<'b>,
+    accounts: &NewGame<'_, 'b>,
+    t: u64,
+    support_bot: bool,
+    user: Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    require(
+        accounts.bot_info.is_some(),
+        ContractError::InvalidInstructionData,
+    )?;
+    let session = accounts
+        .session
+        .ok_or(ContractError::InvalidInstructionData)?;
+
+    let (mut infos, mut metas) = new_game_accounts(accounts);
+    infos.push(session);
+    metas.push(writable_meta(session));
+
+    call(
+        program,
+        &BettingInstruction::SessionNewGame {
+            t,
+            support_bot,
+            user,
+        },
+        infos,
+        metas,
+        signer_seeds,
+    )
+}
+
+fn new_game_accounts<'a, 'b>(
+    accounts: &NewGame<'a, 'b>,
+) -> (Vec<&'a AccountInfo<'b>>, Vec<AccountMeta>) {
+    let mut infos = vec![
+        accounts.payer,
+        accounts.system_program,
+        accounts.pda,
+        accounts.rent_info,
+        accounts.supported_token,
+        accounts.user,
+        accounts.game,
+        accounts.chainlink_program,
+        accounts.feed_account,
+        accounts.source,
+        accounts.destination,
+        accounts.token_program,
+        accounts.token,
+        accounts.token_assoc,
+        accounts.type_price,
+        accounts.stats,
+    ];
+    infos.extend(accounts.bot_info);
+
+    let mut metas = vec![
+        payer_meta(accounts.payer),
+        readonly_meta(accounts.system_program),
+        writable_meta(accounts.pda),
+        readonly_meta(accounts.rent_info),
+        writable_meta(accounts.supported_token),
+        writable_meta(accounts.user),
+        writable_meta(accounts.game),
+        readonly_meta(accounts.chainlink_program),
+        readonly_meta(accounts.feed_account),
+        writable_meta(accounts.source),
+        writable_meta(accounts.destination),
+        readonly_meta(accounts.token_program),
+        readonly_meta(accounts.token),
+        readonly_meta(accounts.token_assoc),
+        writable_meta(accounts.type_price),
+        writable_meta(accounts.stats),
+    ];
+    metas.extend(accounts.bot_info.map(writable_meta));
+
+    (infos, metas)
+}
+
+/// Joins the open game of `user_master` as the second gamer.
+pub fn join_game<'b>(
+    program: &AccountInfo<'b>,
+    accounts: &JoinGame<'_, 'b>,
+    t: u64,
+    support_bot: bool,
+    user_master: Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let (infos, metas) = join_game_accounts(accounts);
+
+    call(
+        program,
+        &BettingInstruction::JoinGame {
+            t,
+            support_bot,
+            user_master,
+        },
+        infos,
+        metas,
+        signer_seeds,
+    )
+}
+
+/// Joins the open game of `user_master` for `user` with `accounts.payer` as their session
+/// delegate. `accounts.bot_info` and `accounts.session` are both required, the session comes last.
+pub fn session_join_game<'b>(
+    program: &AccountInfo<'b>,
+    accounts: &JoinGame<'_, 'b>,
+    t: u64,
+    support_bot: bool,
+    user_master: Pubkey,
+    user: Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    require(
+        accounts.bot_info.is_some(),
+        ContractError::InvalidInstructionData,
+    )?;
+    let session = accounts
+        .session
+        .ok_or(ContractError::InvalidInstructionData)?;
+
+    let (mut infos, mut metas) = join_game_accounts(accounts);
+    infos.push(session);
+    metas.push(writable_meta(session));
+
+    call(
+        program,
+        &BettingInstruction::SessionJoinGame {
+            t,
+            support_bot,
+            user_master,
+            user,
+        },
+        infos,
+        metas,
+        signer_seeds,
+    )
+}
+
+fn join_game_accounts<'a, 'b>(
+    accounts: &JoinGame<'a, 'b>,
+) -> (Vec<&'a AccountInfo<'b>>, Vec<AccountMeta>) {
+    let mut infos = vec![
+        accounts.payer,
+        accounts.system_program,
+        accounts.pda,
+        accounts.rent_info,
+        accounts.supported_token,
+        accounts.user,
+        accounts.user_master,
+        accounts.game,
+        accounts.chainlink_program,
+        accounts.feed_account,
+        accounts.source,
+        accounts.destination,
+        accounts.token_program,
+        accounts.token,
+        accounts.token_assoc,
+        accounts.type_price,
+        accounts.stats,
+    ];
+    infos.extend(accounts.bot_info);
+
+    let mut metas = vec![
+        payer_meta(accounts.payer),
+        readonly_meta(accounts.system_program),
+        writable_meta(accounts.pda),
+        readonly_meta(accounts.rent_info),
+        writable_meta(accounts.supported_token),
+        writable_meta(accounts.user),
+        writable_meta(accounts.user_master),
+        writable_meta(accounts.game),
+        readonly_meta(accounts.chainlink_program),
+        readonly_meta(accounts.feed_account),
+        writable_meta(accounts.source),
+        writable_meta(accounts.destination),
+        readonly_meta(accounts.token_program),
+        readonly_meta(accounts.token),
+        readonly_meta(accounts.token_assoc),
+        writable_meta(accounts.type_price),
+        writable_meta(accounts.stats),
+    ];
+    metas.extend(accounts.bot_info.map(writable_meta));
+
+    (infos, metas)
+}
+
+/// Settles the game of `user`. `accounts.payer` needs the settle role.
+pub fn settle<'b>(
+    program: &AccountInfo<'b>,
+    accounts: &Settle<'_, 'b>,
+    user: Pubkey,
+    winner_address: Pubkey,
+    t: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let infos = vec![
+        accounts.payer,
+        accounts.system_program,
+        accounts.pda,
+        accounts.rent_info,
+        accounts.supported_token,
+        accounts.supported_token1,
+        accounts.user,
+        accounts.user2,
+        accounts.game,
+        accounts.type_price,
+        accounts.stats,
+        accounts.stats1,
+        accounts.role,
+    ];
+
+    let metas = vec![
+        payer_meta(accounts.payer),
+        readonly_meta(accounts.system_program),
+        writable_meta(accounts.pda),
+        readonly_meta(accounts.rent_info),
+        writable_meta(accounts.supported_token),
+        writable_meta(accounts.supported_token1),
+        writable_meta(accounts.user),
+        writable_meta(accounts.user2),
+        writable_meta(accounts.game),
+        writable_meta(accounts.type_price),
+        writable_meta(accounts.stats),
+        writable_meta(accounts.stats1),
+        readonly_meta(accounts.role),
+    ];
+
+    call(
+        program,
+        &BettingInstruction::Settle {
+            user,
+            winner_address,
+            t,
+        },
+        infos,
+        metas,
+        signer_seeds,
+    )
+}
+
+/// Pays out the `index`-th share of the settled game of `user`. For the referral payout
+/// `accounts.referral` is passed through as is; its referral chains come in (user, reward) pairs.
+pub fn distribute<'b>(
+    program: &AccountInfo<'b>,
+    accounts: &Distribute<'_, 'b>,
+    user: Pubkey,
+    index: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let mut infos = vec![
+        accounts.payer,
+        accounts.system_program,
+        accounts.pda,
+        accounts.rent_info,
+        accounts.game,
+        accounts.source,
+        accounts.recipient,
+        accounts.destination,
+        accounts.token_program,
+        accounts.token,
+        accounts.token_assoc,
+    ];
+    infos.extend(accounts.referral.iter());
+
+    let mut metas = vec![
+        payer_meta(accounts.payer),
+        readonly_meta(accounts.system_program),
+        writable_meta(accounts.pda),
+        readonly_meta(accounts.rent_info),
+        writable_meta(accounts.game),
+        writable_meta(accounts.source),
+        writable_meta(accounts.recipient),
+        writable_meta(accounts.destination),
+        readonly_meta(accounts.token_program),
+        readonly_meta(accounts.token),
+        readonly_meta(accounts.token_assoc),
+    ];
+    metas.extend(accounts.referral.iter().map(|info| {
+        if info.is_writable {
+            writable_meta(info)
+        } else {
+            readonly_meta(info)
+        }
+    }));
+
+    call(
+        program,
+        &BettingInstruction::Distribute { user, index },
+        infos,
+        metas,
+        signer_seeds,
+    )
+}
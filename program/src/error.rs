@@ -7,24 +7,126 @@ use thiserror::Error;
 pub enum ContractError {
     #[error("An instruction's data contents was invalid")]
     InvalidInstructionData,
-    #[error("The claimer can't submit this claim")]
-    CannotSubmitThisClaim,
     #[error("Only admin can call this instruction")]
     UnauthorisedAccess,
     #[error("Can`t deserialize data")]
     DeserializeError,
-    #[error("Address is not verified")]
-    UnverifiedAddress,
-    #[error("Staking is inactive")]
-    InactiveStaking,
-    #[error("Nft is not whitelisted")]
-    WhitelistError,
-    #[error("Convert with overflow")]
-    ConvertWithOverflow,
     #[error("Math operation overflow")]
     OperationWithOverflow,
-    #[error("Password is too long")]
-    PasswordError,
+    #[error("Referral code is invalid")]
+    InvalidReferralCode,
+    #[error("Referral code is already taken")]
+    ReferralCodeTaken,
+    #[error("New games are paused")]
+    NewGamesPaused,
+    #[error("Joining games is paused")]
+    JoinGamesPaused,
+    #[error("Settlement is paused")]
+    SettlementPaused,
+    #[error("Token is paused")]
+    TokenPaused,
+    #[error("Game type is paused")]
+    TypePaused,
+    #[error("Bot is suspended")]
+    BotSuspended,
+    #[error("Bot reached its concurrent games limit")]
+    BotGameLimit,
+    #[error("Game type exceeds the bot's stake limit")]
+    BotStakeLimit,
+    #[error("Bot reached its daily volume limit")]
+    BotVolumeLimit,
+    #[error("User is banned")]
+    UserBanned,
+    #[error("Platform is shut down")]
+    Shutdown,
+    #[error("Platform is not shut down")]
+    NotShutdown,
+    #[error("Account is not owned by the program")]
+    InvalidAccountOwner,
+    #[error("User is not registered")]
+    NotRegistered,
+    #[error("Referrer must not be the user itself")]
+    SelfReferral,
+    #[error("User is already in game")]
+    AlreadyInGame,
+    #[error("User is not in this game")]
+    NotInGame,
+    #[error("Can't join own game")]
+    CannotJoinOwnGame,
+    #[error("Token is not supported")]
+    UnsupportedToken,
+    #[error("Wrong price feed for this token")]
+    InvalidFeed,
+    #[error("Game type price doesn't match")]
+    TypePriceMismatch,
+    #[error("User doesn't play against bots")]
+    BotsNotSupported,
+    #[error("Game is already closed")]
+    GameClosed,
+    #[error("Game has already started")]
+    GameAlreadyStarted,
+    #[error("Close delay has not passed yet")]
+    CloseDelayNotPassed,
+    #[error("Winner is not in the game")]
+    InvalidWinner,
+    #[error("Nothing to claim")]
+    NothingToClaim,
+    #[error("Address is not a bot")]
+    NotABot,
+    #[error("Bot is still in game")]
+    BotInGame,
+    #[error("No admin proposed")]
+    NoAdminProposed,
+    #[error("Referral tiers exceed the referral pool")]
+    InvalidReferralTiers,
+    #[error("Config change is already queued")]
+    ConfigChangeAlreadyQueued,
+    #[error("No config change queued")]
+    NoConfigChangeQueued,
+    #[error("Config change is timelocked")]
+    ConfigChangeTimelocked,
+    #[error("Account is already up to date")]
+    AccountUpToDate,
+    #[error("Game is not settled")]
+    GameNotSettled,
+    #[error("Payout is already distributed")]
+    PayoutDistributed,
+    #[error("Previous game still has payouts to distribute")]
+    PayoutsPending,
+    #[error("Nickname is too long")]
+    NicknameTooLong,
+    #[error("Avatar URI is too long")]
+    AvatarUriTooLong,
+    #[error("User is already registered")]
+    AlreadyRegistered,
+    #[error("Referrer can't be changed anymore")]
+    ReferrerLocked,
+    #[error("Bots are removed with RemoveBot")]
+    CannotDeregisterBot,
+    #[error("User is self-excluded")]
+    SelfExcluded,
+    #[error("Game type exceeds the user's stake limit")]
+    StakeLimit,
+    #[error("User reached the daily games limit")]
+    DailyGamesLimit,
+    #[error("Game would exceed the user's daily loss limit")]
+    DailyLossLimit,
+    #[error("Session is missing or belongs to another delegate")]
+    InvalidSession,
+    #[error("Session has expired")]
+    SessionExpired,
+    #[error("Game exceeds the session limits")]
+    SessionLimit,
+    #[error("Fees exceed 100%")]
+    InvalidFees,
+    #[error("Account has to be migrated first")]
+    MigrationRequired,
+    #[error("Referral rewards have to be distributed first")]
+    ReferrerPayoutPending,
+    #[error("Limits are still in effect")]
+    LimitsActive,
+    #[error("Delay exceeds its maximum")]
+    InvalidDelays,
 }
 
 impl From<ContractError> for ProgramError {
@@ -0,0 +1,269 @@
+use crate::state::structs::{AccountType, Config, Limits};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::log::sol_log_data;
+use solana_program::pubkey::Pubkey;
+
+/// Logged with `sol_log_data` after every state change so indexers don't have to parse transfers.
+/// `game` is always the address of the game PDA.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum BettingEvent {
+    Initialized {
+        #[allow(dead_code)]
+        admin: Pubkey,
+    },
+    PauseFlagsChanged {
+        #[allow(dead_code)]
+        flags: u8,
+    },
+    EmergencyShutdown {
+        #[allow(dead_code)]
+        shutdown: bool,
+    },
+    AdminProposed {
+        #[allow(dead_code)]
+        admin: Pubkey,
+    },
+    AdminChanged {
+        #[allow(dead_code)]
+        admin: Pubkey,
+    },
+    RoleChanged {
+        #[allow(dead_code)]
+        address: Pubkey,
+        #[allow(dead_code)]
+        permissions: u8,
+    },
+    ConfigChangeQueued {
+        #[allow(dead_code)]
+        config: Config,
+        #[allow(dead_code)]
+        execute_after: u64,
+    },
+    ConfigChanged {
+        #[allow(dead_code)]
+        config: Config,
+    },
+    ConfigChangeCancelled,
+    TokenListed {
+        #[allow(dead_code)]
+        mint: Pubkey,
+        #[allow(dead_code)]
+        feed: Pubkey,
+        #[allow(dead_code)]
+        is_stablecoin: bool,
+    },
+    TokenPaused {
+        #[allow(dead_code)]
+        mint: Pubkey,
+        #[allow(dead_code)]
+        paused: bool,
+    },
+    TypePriceChanged {
+        #[allow(dead_code)]
+        t: u64,
+        #[allow(dead_code)]
+        price: u64,
+    },
+    TypePaused {
+        #[allow(dead_code)]
+        t: u64,
+        #[allow(dead_code)]
+        paused: bool,
+    },
+    UserRegistered {
+        #[allow(dead_code)]
+        user: Pubkey,
+        #[allow(dead_code)]
+        referrer: Pubkey,
+    },
+    UserBanned {
+        #[allow(dead_code)]
+        user: Pubkey,
+        #[allow(dead_code)]
+        reason: u8,
+        #[allow(dead_code)]
+        expiry: u64,
+    },
+    UserUnbanned {
+        #[allow(dead_code)]
+        user: Pubkey,
+    },
+    BotUpdated {
+        #[allow(dead_code)]
+        bot: Pubkey,
+        #[allow(dead_code)]
+        max_games: u64,
+        #[allow(dead_code)]
+        max_type_price: u64,
+        #[allow(dead_code)]
+        daily_volume_limit: u64,
+    },
+    BotSuspended {
+        #[allow(dead_code)]
+        bot: Pubkey,
+        #[allow(dead_code)]
+        suspended: bool,
+    },
+    BotRemoved {
+        #[allow(dead_code)]
+        bot: Pubkey,
+    },
+    GameCreated {
+        #[allow(dead_code)]
+        game: Pubkey,
+        #[allow(dead_code)]
+        gamer: Pubkey,
+        #[allow(dead_code)]
+        token: Pubkey,
+        #[allow(dead_code)]
+        amount: u64,
+        #[allow(dead_code)]
+        type_price: u64,
+    },
+    GameJoined {
+        #[allow(dead_code)]
+        game: Pubkey,
+        #[allow(dead_code)]
+        gamer: Pubkey,
+        #[allow(dead_code)]
+        token: Pubkey,
+        #[allow(dead_code)]
+        amount: u64,
+    },
+    /// Fees are only taken from the first stake: `payout1 = amount1 - admin_fee - referrer_fee`.
+    /// Nothing is transferred yet, the payouts follow with `Distribute`.
+    GameSettled {
+        #[allow(dead_code)]
+        game: Pubkey,
+        #[allow(dead_code)]
+        winner: Pubkey,
+        #[allow(dead_code)]
+        token1: Pubkey,
+        #[allow(dead_code)]
+        amount1: u64,
+        #[allow(dead_code)]
+        token2: Pubkey,
+        #[allow(dead_code)]
+        amount2: u64,
+        #[allow(dead_code)]
+        admin_fee: u64,
+        #[allow(dead_code)]
+        referrer_fee: u64,
+        #[allow(dead_code)]
+        payout1: u64,
+        #[allow(dead_code)]
+        payout2: u64,
+    },
+    /// Both stakes went back to their players in full; `gamer2` is empty for a game nobody joined.
+    GameCancelled {
+        #[allow(dead_code)]
+        game: Pubkey,
+        #[allow(dead_code)]
+        gamer1: Pubkey,
+        #[allow(dead_code)]
+        amount1: u64,
+        #[allow(dead_code)]
+        gamer2: Pubkey,
+        #[allow(dead_code)]
+        amount2: u64,
+    },
+    ReferralRewardsClaimed {
+        #[allow(dead_code)]
+        referrer: Pubkey,
+        #[allow(dead_code)]
+        mint: Pubkey,
+        #[allow(dead_code)]
+        amount: u64,
+    },
+    ReferralCodeClaimed {
+        #[allow(dead_code)]
+        code: String,
+        #[allow(dead_code)]
+        owner: Pubkey,
+    },
+    ReferralCodeTransferred {
+        #[allow(dead_code)]
+        code: String,
+        #[allow(dead_code)]
+        owner: Pubkey,
+    },
+    ReferralCodeReleased {
+        #[allow(dead_code)]
+        code: String,
+    },
+    AccountMigrated {
+        #[allow(dead_code)]
+        account: Pubkey,
+        #[allow(dead_code)]
+        account_type: AccountType,
+    },
+    PayoutDistributed {
+        #[allow(dead_code)]
+        game: Pubkey,
+        #[allow(dead_code)]
+        index: u8,
+        #[allow(dead_code)]
+        recipient: Pubkey,
+        #[allow(dead_code)]
+        token: Pubkey,
+        #[allow(dead_code)]
+        amount: u64,
+    },
+    CredentialHashSet {
+        #[allow(dead_code)]
+        user: Pubkey,
+    },
+    ProfileUpdated {
+        #[allow(dead_code)]
+        user: Pubkey,
+        #[allow(dead_code)]
+        nickname: String,
+        #[allow(dead_code)]
+        avatar_uri: String,
+    },
+    ReferrerChanged {
+        #[allow(dead_code)]
+        user: Pubkey,
+        #[allow(dead_code)]
+        referrer: Pubkey,
+    },
+    UserDeregistered {
+        #[allow(dead_code)]
+        user: Pubkey,
+    },
+    LimitsChanged {
+        #[allow(dead_code)]
+        user: Pubkey,
+        #[allow(dead_code)]
+        limits: Limits,
+        #[allow(dead_code)]
+        pending_limits: Limits,
+        #[allow(dead_code)]
+        pending_limits_at: u64,
+    },
+    SessionCreated {
+        #[allow(dead_code)]
+        user: Pubkey,
+        #[allow(dead_code)]
+        delegate: Pubkey,
+        #[allow(dead_code)]
+        expires_at: u64,
+        #[allow(dead_code)]
+        max_stake_per_game: u64,
+        #[allow(dead_code)]
+        max_total: u64,
+    },
+    SessionRevoked {
+        #[allow(dead_code)]
+        user: Pubkey,
+    },
+}
+
+impl BettingEvent {
+    pub fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+
+        Ok(())
+    }
+}
@@ -1,11 +1,24 @@
+use crate::consts::{MAX_REFERRAL_DEPTH, PAYOUT_REFERRERS};
+use crate::pda::{
+    find_betting_address, find_bot_info_address, find_bot_registry_address, find_config_address,
+    find_game_address, find_referral_code_address, find_referral_reward_address, find_role_address,
+    find_session_address, find_stats_address, find_supported_token_address,
+    find_type_price_address, find_user_address,
+};
+use crate::state::structs::{AccountType, Config, Game, Limits};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::bpf_loader_upgradeable;
+use solana_program::instruction::{AccountMeta, Instruction};
 use solana_program::pubkey::Pubkey;
+use solana_program::system_program;
+use solana_program::sysvar::rent;
+use spl_associated_token_account::get_associated_token_address;
 
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum BettingInstruction {
     Init {
         #[allow(dead_code)]
-        manager: Pubkey,
+        admin: Pubkey,
         #[allow(dead_code)]
         supported_token: Pubkey,
         #[allow(dead_code)]
@@ -13,10 +26,6 @@ pub enum BettingInstruction {
         #[allow(dead_code)]
         is_stablecoin: bool,
     },
-    ChangeCloseDelay {
-        #[allow(dead_code)]
-        new_delay: u64,
-    },
     LockBets,
     UnlockBets,
     AddSupportedToken {
@@ -29,40 +38,28 @@ pub enum BettingInstruction {
     },
     Registration {
         #[allow(dead_code)]
-        referrer: Pubkey,
-        #[allow(dead_code)]
-        password: String,
-    },
-    NewManager {
-        #[allow(dead_code)]
-        manager: Pubkey,
-    },
-    SetGlobalFee {
+        referrer: Referrer,
         #[allow(dead_code)]
-        fee: u64,
+        credential_hash: Option<[u8; 32]>,
     },
-    SetAdminFee {
+    AddBot {
         #[allow(dead_code)]
-        fee: u64,
-    },
-    SetWinnerFee {
+        bot: Pubkey,
         #[allow(dead_code)]
-        fee: u64,
-    },
-    SetTransactionFee {
+        max_games: u64,
         #[allow(dead_code)]
-        fee: u64,
-    },
-    AddBot {
+        max_type_price: u64,
         #[allow(dead_code)]
-        bot: Pubkey,
+        daily_volume_limit: u64,
     },
+    /// Accounts as in `cpi::NewGame`.
     NewGame {
         #[allow(dead_code)]
         t: u64,
         #[allow(dead_code)]
         support_bot: bool,
     },
+    /// Accounts as in `cpi::JoinGame`.
     JoinGame {
         #[allow(dead_code)]
         t: u64,
@@ -76,7 +73,8 @@ pub enum BettingInstruction {
         user: Pubkey,
     },
     ManuallyClose,
-    Close {
+    /// Accounts as in `cpi::Settle`, followed by the referral chain.
+    Settle {
         #[allow(dead_code)]
         user: Pubkey,
         #[allow(dead_code)]
@@ -90,4 +88,1270 @@ pub enum BettingInstruction {
         #[allow(dead_code)]
         price: u64,
     },
+    ClaimReferralRewards,
+    ClaimReferralCode {
+        #[allow(dead_code)]
+        code: String,
+    },
+    TransferReferralCode {
+        #[allow(dead_code)]
+        code: String,
+        #[allow(dead_code)]
+        new_owner: Pubkey,
+    },
+    ReleaseReferralCode {
+        #[allow(dead_code)]
+        code: String,
+    },
+    ProposeAdmin {
+        #[allow(dead_code)]
+        admin: Pubkey,
+    },
+    AcceptAdmin,
+    GrantRole {
+        #[allow(dead_code)]
+        address: Pubkey,
+        #[allow(dead_code)]
+        permissions: u8,
+    },
+    RevokeRole {
+        #[allow(dead_code)]
+        address: Pubkey,
+        #[allow(dead_code)]
+        permissions: u8,
+    },
+    QueueConfigChange {
+        #[allow(dead_code)]
+        config: Config,
+    },
+    ExecuteConfigChange,
+    CancelConfigChange,
+    SetPauseFlags {
+        #[allow(dead_code)]
+        flags: u8,
+    },
+    SetTokenPaused {
+        #[allow(dead_code)]
+        supported_token: Pubkey,
+        #[allow(dead_code)]
+        paused: bool,
+    },
+    SetTypePaused {
+        #[allow(dead_code)]
+        t: u64,
+        #[allow(dead_code)]
+        paused: bool,
+    },
+    RemoveBot {
+        #[allow(dead_code)]
+        bot: Pubkey,
+    },
+    SuspendBot {
+        #[allow(dead_code)]
+        bot: Pubkey,
+        #[allow(dead_code)]
+        suspended: bool,
+    },
+    BanUser {
+        #[allow(dead_code)]
+        user: Pubkey,
+        #[allow(dead_code)]
+        reason: u8,
+        #[allow(dead_code)]
+        expiry: u64,
+    },
+    UnbanUser {
+        #[allow(dead_code)]
+        user: Pubkey,
+    },
+    MigrateAccount {
+        #[allow(dead_code)]
+        account_type: AccountType,
+        #[allow(dead_code)]
+        t: u64,
+    },
+    EmergencyShutdown {
+        #[allow(dead_code)]
+        shutdown: bool,
+    },
+    EmergencyRefund {
+        #[allow(dead_code)]
+        game: Pubkey,
+    },
+    /// Accounts as in `cpi::Distribute`.
+    Distribute {
+        #[allow(dead_code)]
+        user: Pubkey,
+        #[allow(dead_code)]
+        index: u8,
+    },
+    /// `None` removes the stored hash.
+    SetCredentialHash {
+        #[allow(dead_code)]
+        credential_hash: Option<[u8; 32]>,
+    },
+    UpdateProfile {
+        #[allow(dead_code)]
+        nickname: String,
+        #[allow(dead_code)]
+        avatar_uri: String,
+    },
+    ChangeReferrer {
+        #[allow(dead_code)]
+        referrer: Referrer,
+    },
+    Deregister,
+    SetLimits {
+        #[allow(dead_code)]
+        limits: Limits,
+    },
+    /// `max_stake_per_game` and `max_total` are counted in `TypePrice::price` units, the game
+    /// type a bet is placed at, not in token amounts; a limit of 0 means unlimited. The tokens
+    /// the delegate can stake are capped by the SPL allowance the user gives the betting PDA.
+    CreateSession {
+        #[allow(dead_code)]
+        delegate: Pubkey,
+        #[allow(dead_code)]
+        expires_at: u64,
+        #[allow(dead_code)]
+        max_stake_per_game: u64,
+        #[allow(dead_code)]
+        max_total: u64,
+    },
+    RevokeSession,
+    /// `NewGame` signed by the session delegate of `user`. Accounts as in `cpi::NewGame`, with
+    /// the session last.
+    SessionNewGame {
+        #[allow(dead_code)]
+        t: u64,
+        #[allow(dead_code)]
+        support_bot: bool,
+        #[allow(dead_code)]
+        user: Pubkey,
+    },
+    /// `JoinGame` signed by the session delegate of `user`. Accounts as in `cpi::JoinGame`, with
+    /// the session last.
+    SessionJoinGame {
+        #[allow(dead_code)]
+        t: u64,
+        #[allow(dead_code)]
+        support_bot: bool,
+        #[allow(dead_code)]
+        user_master: Pubkey,
+        #[allow(dead_code)]
+        user: Pubkey,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum Referrer {
+    Wallet(#[allow(dead_code)] Pubkey),
+    Code(#[allow(dead_code)] String),
+}
+
+pub fn init(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    admin: Pubkey,
+    supported_token: Pubkey,
+    feed: Pubkey,
+    is_stablecoin: bool,
+) -> Instruction {
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::Init {
+            admin,
+            supported_token,
+            feed,
+            is_stablecoin,
+        },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(
+                find_supported_token_address(program_id, &supported_token).0,
+                false,
+            ),
+            AccountMeta::new_readonly(program_data, false),
+        ],
+    )
+}
+
+pub fn lock_bets(program_id: &Pubkey, payer: &Pubkey) -> Instruction {
+    admin_instruction(program_id, payer, BettingInstruction::LockBets)
+}
+
+pub fn unlock_bets(program_id: &Pubkey, payer: &Pubkey) -> Instruction {
+    admin_instruction(program_id, payer, BettingInstruction::UnlockBets)
+}
+
+pub fn add_supported_token(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    supported_token: Pubkey,
+    feed: Pubkey,
+    is_stablecoin: bool,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::AddSupportedToken {
+            supported_token,
+            feed,
+            is_stablecoin,
+        },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(
+                find_supported_token_address(program_id, &supported_token).0,
+                false,
+            ),
+            AccountMeta::new_readonly(find_role_address(program_id, payer).0, false),
+        ],
+    )
+}
+
+pub fn registration(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    referrer: Referrer,
+    credential_hash: Option<[u8; 32]>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(rent::id(), false),
+        AccountMeta::new(find_user_address(program_id, payer).0, false),
+    ];
+
+    if let Referrer::Code(code) = &referrer {
+        accounts.push(AccountMeta::new_readonly(
+            find_referral_code_address(program_id, code).0,
+            false,
+        ));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::Registration {
+            referrer,
+            credential_hash,
+        },
+        accounts,
+    )
+}
+
+pub fn add_bot(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    bot: Pubkey,
+    max_games: u64,
+    max_type_price: u64,
+    daily_volume_limit: u64,
+) -> Instruction {
+    bot_instruction(
+        program_id,
+        payer,
+        &bot,
+        BettingInstruction::AddBot {
+            bot,
+            max_games,
+            max_type_price,
+            daily_volume_limit,
+        },
+    )
+}
+
+/// Opens a game of type `t` staking `token`. `feed` is the Chainlink feed of `token`.
+pub fn new_game(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    token: &Pubkey,
+    chainlink_program: &Pubkey,
+    feed: &Pubkey,
+    t: u64,
+    support_bot: bool,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::NewGame { t, support_bot },
+        new_game_accounts(program_id, payer, payer, token, chainlink_program, feed, t),
+    )
+}
+
+/// Opens a game of type `t` for `user`, signed by the delegate of their session. The stake is
+/// taken from the allowance `user` approved to the betting PDA.
+#[allow(clippy::too_many_arguments)]
+pub fn session_new_game(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    user: Pubkey,
+    token: &Pubkey,
+    chainlink_program: &Pubkey,
+    feed: &Pubkey,
+    t: u64,
+    support_bot: bool,
+) -> Instruction {
+    let mut accounts =
+        new_game_accounts(program_id, payer, &user, token, chainlink_program, feed, t);
+    accounts.push(AccountMeta::new(
+        find_session_address(program_id, &user).0,
+        false,
+    ));
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::SessionNewGame {
+            t,
+            support_bot,
+            user,
+        },
+        accounts,
+    )
+}
+
+/// Joins the open game of `user_master`, staking `token`.
+#[allow(clippy::too_many_arguments)]
+pub fn join_game(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    user_master: Pubkey,
+    token: &Pubkey,
+    chainlink_program: &Pubkey,
+    feed: &Pubkey,
+    t: u64,
+    support_bot: bool,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::JoinGame {
+            t,
+            support_bot,
+            user_master,
+        },
+        join_game_accounts(
+            program_id,
+            payer,
+            payer,
+            &user_master,
+            token,
+            chainlink_program,
+            feed,
+            t,
+        ),
+    )
+}
+
+/// Joins the open game of `user_master` for `user`, signed by the delegate of their session.
+#[allow(clippy::too_many_arguments)]
+pub fn session_join_game(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    user: Pubkey,
+    user_master: Pubkey,
+    token: &Pubkey,
+    chainlink_program: &Pubkey,
+    feed: &Pubkey,
+    t: u64,
+    support_bot: bool,
+) -> Instruction {
+    let mut accounts = join_game_accounts(
+        program_id,
+        payer,
+        &user,
+        &user_master,
+        token,
+        chainlink_program,
+        feed,
+        t,
+    );
+    accounts.push(AccountMeta::new(
+        find_session_address(program_id, &user).0,
+        false,
+    ));
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::SessionJoinGame {
+            t,
+            support_bot,
+            user_master,
+            user,
+        },
+        accounts,
+    )
+}
+
+/// Force closes `game`, refunding the second gamer as well once the game has started.
+pub fn forced_close(program_id: &Pubkey, payer: &Pubkey, game: &Game) -> Instruction {
+    let user = game.gamer1;
+    let (game_address, _) = find_game_address(program_id, &user);
+
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(find_betting_address(program_id).0, false),
+        AccountMeta::new_readonly(rent::id(), false),
+        AccountMeta::new(
+            find_supported_token_address(program_id, &game.token1).0,
+            false,
+        ),
+        AccountMeta::new(find_user_address(program_id, &user).0, false),
+        AccountMeta::new(user, false),
+        AccountMeta::new(game_address, false),
+        AccountMeta::new(
+            get_associated_token_address(&game_address, &game.token1),
+            false,
+        ),
+        AccountMeta::new(get_associated_token_address(&user, &game.token1), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(game.token1, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(find_role_address(program_id, payer).0, false),
+    ];
+    accounts.extend(second_gamer_refund(program_id, &game_address, game));
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::ForcedClose { user },
+        accounts,
+    )
+}
+
+/// Closes the payer's game that nobody joined. `admin` receives the close fee.
+pub fn manually_close(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    token: &Pubkey,
+    admin: &Pubkey,
+) -> Instruction {
+    let (game, _) = find_game_address(program_id, payer);
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::ManuallyClose,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(find_supported_token_address(program_id, token).0, false),
+            AccountMeta::new(find_user_address(program_id, payer).0, false),
+            AccountMeta::new(game, false),
+            AccountMeta::new(get_associated_token_address(&game, token), false),
+            AccountMeta::new(get_associated_token_address(payer, token), false),
+            AccountMeta::new(*admin, false),
+            AccountMeta::new(get_associated_token_address(admin, token), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(*token, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new(find_stats_address(program_id, token).0, false),
+        ],
+    )
+}
+
+/// Settles `game`.
+pub fn settle(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    game: &Game,
+    winner_address: Pubkey,
+    t: u64,
+) -> Instruction {
+    let user = game.gamer1;
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::Settle {
+            user,
+            winner_address,
+            t,
+        },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(
+                find_supported_token_address(program_id, &game.token1).0,
+                false,
+            ),
+            AccountMeta::new(
+                find_supported_token_address(program_id, &game.token2).0,
+                false,
+            ),
+            AccountMeta::new(find_user_address(program_id, &user).0, false),
+            AccountMeta::new(find_user_address(program_id, &game.gamer2).0, false),
+            AccountMeta::new(find_game_address(program_id, &user).0, false),
+            AccountMeta::new(find_type_price_address(program_id, t).0, false),
+            AccountMeta::new(find_stats_address(program_id, &game.token1).0, false),
+            AccountMeta::new(find_stats_address(program_id, &game.token2).0, false),
+            AccountMeta::new_readonly(find_role_address(program_id, payer).0, false),
+        ],
+    )
+}
+
+/// Pushes the `index`-th payout of the settled game of `user` to `recipient`.
+pub fn distribute(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    user: Pubkey,
+    index: u8,
+    recipient: &Pubkey,
+    token: &Pubkey,
+) -> Instruction {
+    let (game, _) = find_game_address(program_id, &user);
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::Distribute { user, index },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(game, false),
+            AccountMeta::new(get_associated_token_address(&game, token), false),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new(get_associated_token_address(recipient, token), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(*token, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+    )
+}
+
+/// Pushes the referral payout of the settled `game` into the vault. `referrers1` and
+/// `referrers2` are the referral chains of the two gamers, starting with their direct referrer.
+/// The payer opens the reward PDAs of referrers that don't have one yet.
+pub fn distribute_referrers(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    game: &Game,
+    referrers1: &[Pubkey],
+    referrers2: &[Pubkey],
+) -> Instruction {
+    let first_reward = |referrers: &[Pubkey]| {
+        let referrer = referrers.first().copied().unwrap_or_default();
+        find_referral_reward_address(program_id, &referrer, &game.token1).0
+    };
+
+    let mut instruction = distribute(
+        program_id,
+        payer,
+        game.gamer1,
+        PAYOUT_REFERRERS,
+        &find_betting_address(program_id).0,
+        &game.token1,
+    );
+    instruction.accounts.extend(vec![
+        AccountMeta::new(find_stats_address(program_id, &game.token1).0, false),
+        AccountMeta::new_readonly(find_user_address(program_id, &game.gamer1).0, false),
+        AccountMeta::new_readonly(find_user_address(program_id, &game.gamer2).0, false),
+        AccountMeta::new(first_reward(referrers1), false),
+        AccountMeta::new(first_reward(referrers2), false),
+    ]);
+    instruction.accounts.extend(referral_chain(
+        program_id,
+        &game.gamer1,
+        referrers1,
+        &game.token1,
+    ));
+    instruction.accounts.extend(referral_chain(
+        program_id,
+        &game.gamer2,
+        referrers2,
+        &game.token1,
+    ));
+
+    instruction
+}
+
+pub fn set_type_price(program_id: &Pubkey, payer: &Pubkey, t: u64, price: u64) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::SetTypePrice { t, price },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+            AccountMeta::new(find_type_price_address(program_id, t).0, false),
+            AccountMeta::new_readonly(find_role_address(program_id, payer).0, false),
+        ],
+    )
+}
+
+pub fn claim_referral_rewards(program_id: &Pubkey, payer: &Pubkey, token: &Pubkey) -> Instruction {
+    let (betting, _) = find_betting_address(program_id);
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::ClaimReferralRewards,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(betting, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(
+                find_referral_reward_address(program_id, payer, token).0,
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(&betting, token), false),
+            AccountMeta::new(get_associated_token_address(payer, token), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(*token, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+    )
+}
+
+pub fn claim_referral_code(program_id: &Pubkey, payer: &Pubkey, code: String) -> Instruction {
+    referral_code_instruction(
+        program_id,
+        payer,
+        &code,
+        BettingInstruction::ClaimReferralCode { code: code.clone() },
+    )
+}
+
+pub fn transfer_referral_code(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    code: String,
+    new_owner: Pubkey,
+) -> Instruction {
+    referral_code_instruction(
+        program_id,
+        payer,
+        &code,
+        BettingInstruction::TransferReferralCode {
+            code: code.clone(),
+            new_owner,
+        },
+    )
+}
+
+pub fn release_referral_code(program_id: &Pubkey, payer: &Pubkey, code: String) -> Instruction {
+    referral_code_instruction(
+        program_id,
+        payer,
+        &code,
+        BettingInstruction::ReleaseReferralCode { code: code.clone() },
+    )
+}
+
+pub fn propose_admin(program_id: &Pubkey, payer: &Pubkey, admin: Pubkey) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::ProposeAdmin { admin },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn accept_admin(program_id: &Pubkey, payer: &Pubkey) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::AcceptAdmin,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn grant_role(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    address: Pubkey,
+    permissions: u8,
+) -> Instruction {
+    role_instruction(
+        program_id,
+        payer,
+        &address,
+        BettingInstruction::GrantRole {
+            address,
+            permissions,
+        },
+    )
+}
+
+pub fn revoke_role(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    address: Pubkey,
+    permissions: u8,
+) -> Instruction {
+    role_instruction(
+        program_id,
+        payer,
+        &address,
+        BettingInstruction::RevokeRole {
+            address,
+            permissions,
+        },
+    )
+}
+
+pub fn queue_config_change(program_id: &Pubkey, payer: &Pubkey, config: Config) -> Instruction {
+    config_instruction(
+        program_id,
+        payer,
+        BettingInstruction::QueueConfigChange { config },
+    )
+}
+
+pub fn execute_config_change(program_id: &Pubkey, payer: &Pubkey) -> Instruction {
+    config_instruction(program_id, payer, BettingInstruction::ExecuteConfigChange)
+}
+
+pub fn cancel_config_change(program_id: &Pubkey, payer: &Pubkey) -> Instruction {
+    config_instruction(program_id, payer, BettingInstruction::CancelConfigChange)
+}
+
+pub fn set_pause_flags(program_id: &Pubkey, payer: &Pubkey, flags: u8) -> Instruction {
+    pause_instruction(
+        program_id,
+        payer,
+        None,
+        BettingInstruction::SetPauseFlags { flags },
+    )
+}
+
+pub fn set_token_paused(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    supported_token: Pubkey,
+    paused: bool,
+) -> Instruction {
+    pause_instruction(
+        program_id,
+        payer,
+        Some(find_supported_token_address(program_id, &supported_token).0),
+        BettingInstruction::SetTokenPaused {
+            supported_token,
+            paused,
+        },
+    )
+}
+
+pub fn set_type_paused(program_id: &Pubkey, payer: &Pubkey, t: u64, paused: bool) -> Instruction {
+    pause_instruction(
+        program_id,
+        payer,
+        Some(find_type_price_address(program_id, t).0),
+        BettingInstruction::SetTypePaused { t, paused },
+    )
+}
+
+pub fn remove_bot(program_id: &Pubkey, payer: &Pubkey, bot: Pubkey) -> Instruction {
+    bot_instruction(
+        program_id,
+        payer,
+        &bot,
+        BettingInstruction::RemoveBot { bot },
+    )
+}
+
+pub fn suspend_bot(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    bot: Pubkey,
+    suspended: bool,
+) -> Instruction {
+    bot_instruction(
+        program_id,
+        payer,
+        &bot,
+        BettingInstruction::SuspendBot { bot, suspended },
+    )
+}
+
+pub fn ban_user(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    user: Pubkey,
+    reason: u8,
+    expiry: u64,
+) -> Instruction {
+    ban_instruction(
+        program_id,
+        payer,
+        &user,
+        BettingInstruction::BanUser {
+            user,
+            reason,
+            expiry,
+        },
+    )
+}
+
+pub fn unban_user(program_id: &Pubkey, payer: &Pubkey, user: Pubkey) -> Instruction {
+    ban_instruction(
+        program_id,
+        payer,
+        &user,
+        BettingInstruction::UnbanUser { user },
+    )
+}
+
+/// `account` is the address of the account to migrate; `t` only matters for `TypePrice`.
+/// Accounts in an older layout stay readable, but every instruction that writes one fails with
+/// `MigrationRequired` until this has run.
+pub fn migrate_account(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    account: &Pubkey,
+    account_type: AccountType,
+    t: u64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::MigrateAccount { account_type, t },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(*account, false),
+        ],
+    )
+}
+
+pub fn emergency_shutdown(program_id: &Pubkey, payer: &Pubkey, shutdown: bool) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::EmergencyShutdown { shutdown },
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Refunds both stakes of `game` while the program is shut down.
+pub fn emergency_refund(program_id: &Pubkey, payer: &Pubkey, game: &Game) -> Instruction {
+    let (game_address, _) = find_game_address(program_id, &game.gamer1);
+
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(find_betting_address(program_id).0, false),
+        AccountMeta::new_readonly(rent::id(), false),
+        AccountMeta::new(find_user_address(program_id, &game.gamer1).0, false),
+        AccountMeta::new(game.gamer1, false),
+        AccountMeta::new(game_address, false),
+        AccountMeta::new(
+            get_associated_token_address(&game_address, &game.token1),
+            false,
+        ),
+        AccountMeta::new(
+            get_associated_token_address(&game.gamer1, &game.token1),
+            false,
+        ),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(game.token1, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+    ];
+    accounts.extend(second_gamer_refund(program_id, &game_address, game));
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::EmergencyRefund { game: game_address },
+        accounts,
+    )
+}
+
+pub fn set_credential_hash(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    credential_hash: Option<[u8; 32]>,
+) -> Instruction {
+    profile_instruction(
+        program_id,
+        payer,
+        BettingInstruction::SetCredentialHash { credential_hash },
+    )
+}
+
+pub fn update_profile(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    nickname: String,
+    avatar_uri: String,
+) -> Instruction {
+    profile_instruction(
+        program_id,
+        payer,
+        BettingInstruction::UpdateProfile {
+            nickname,
+            avatar_uri,
+        },
+    )
+}
+
+pub fn change_referrer(program_id: &Pubkey, payer: &Pubkey, referrer: Referrer) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(find_user_address(program_id, payer).0, false),
+    ];
+
+    if let Referrer::Code(code) = &referrer {
+        accounts.push(AccountMeta::new_readonly(
+            find_referral_code_address(program_id, code).0,
+            false,
+        ));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &BettingInstruction::ChangeReferrer { referrer },
+        accounts,
+    )
+}
+
+pub fn deregister(program_id: &Pubkey, payer: &Pubkey) -> Instruction {
+    profile_instruction(program_id, payer, BettingInstruction::Deregister)
+}
+
+pub fn set_limits(program_id: &Pubkey, payer: &Pubkey, limits: Limits) -> Instruction {
+    profile_instruction(program_id, payer, BettingInstruction::SetLimits { limits })
+}
+
+/// Opens a session for `delegate`. The limits are in `TypePrice::price` units, see
+/// `BettingInstruction::CreateSession`.
+pub fn create_session(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    delegate: Pubkey,
+    expires_at: u64,
+    max_stake_per_game: u64,
+    max_total: u64,
+) -> Instruction {
+    session_instruction(
+        program_id,
+        payer,
+        BettingInstruction::CreateSession {
+            delegate,
+            expires_at,
+            max_stake_per_game,
+            max_total,
+        },
+    )
+}
+
+pub fn revoke_session(program_id: &Pubkey, payer: &Pubkey) -> Instruction {
+    session_instruction(program_id, payer, BettingInstruction::RevokeSession)
+}
+
+/// The accounts the on-chain referral walk reads after the first reward: the User PDA of every
+/// referrer but the last one, each followed by the reward PDA of the referrer above it.
+/// `referrers` starts with the player's direct referrer.
+pub fn referral_chain(
+    program_id: &Pubkey,
+    player: &Pubkey,
+    referrers: &[Pubkey],
+    mint: &Pubkey,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![];
+    let mut visited = vec![*player];
+
+    for (level, referrer) in referrers.iter().take(MAX_REFERRAL_DEPTH).enumerate() {
+        if *referrer == Pubkey::default() || visited.contains(referrer) {
+            break;
+        }
+
+        if level != 0 {
+            accounts.push(AccountMeta::new(
+                find_referral_reward_address(program_id, referrer, mint).0,
+                false,
+            ));
+        }
+
+        visited.push(*referrer);
+
+        if level + 1 == MAX_REFERRAL_DEPTH {
+            break;
+        }
+
+        accounts.push(AccountMeta::new_readonly(
+            find_user_address(program_id, referrer).0,
+            false,
+        ));
+    }
+
+    accounts
+}
+
+fn second_gamer_refund(
+    program_id: &Pubkey,
+    game_address: &Pubkey,
+    game: &Game,
+) -> Vec<AccountMeta> {
+    if game.gamer2 == Pubkey::default() {
+        return vec![];
+    }
+
+    vec![
+        AccountMeta::new(find_user_address(program_id, &game.gamer2).0, false),
+        AccountMeta::new(game.gamer2, false),
+        AccountMeta::new(
+            get_associated_token_address(game_address, &game.token2),
+            false,
+        ),
+        AccountMeta::new(
+            get_associated_token_address(&game.gamer2, &game.token2),
+            false,
+        ),
+        AccountMeta::new_readonly(game.token2, false),
+    ]
+}
+
+fn admin_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    instruction: BettingInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(find_role_address(program_id, payer).0, false),
+        ],
+    )
+}
+
+fn bot_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    bot: &Pubkey,
+    instruction: BettingInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(find_user_address(program_id, bot).0, false),
+            AccountMeta::new(find_bot_info_address(program_id, bot).0, false),
+            AccountMeta::new(find_bot_registry_address(program_id).0, false),
+            AccountMeta::new_readonly(find_role_address(program_id, payer).0, false),
+        ],
+    )
+}
+
+fn referral_code_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    code: &str,
+    instruction: BettingInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(find_referral_code_address(program_id, code).0, false),
+        ],
+    )
+}
+
+fn role_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    address: &Pubkey,
+    instruction: BettingInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(find_role_address(program_id, address).0, false),
+        ],
+    )
+}
+
+fn config_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    instruction: BettingInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(find_betting_address(program_id).0, false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(find_role_address(program_id, payer).0, false),
+        ],
+    )
+}
+
+fn pause_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    target: Option<Pubkey>,
+    instruction: BettingInstruction,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(find_betting_address(program_id).0, false),
+    ];
+    if let Some(target) = target {
+        accounts.push(AccountMeta::new(target, false));
+    }
+    accounts.push(AccountMeta::new_readonly(
+        find_role_address(program_id, payer).0,
+        false,
+    ));
+
+    Instruction::new_with_borsh(*program_id, &instruction, accounts)
+}
+
+fn ban_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    user: &Pubkey,
+    instruction: BettingInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(find_betting_address(program_id).0, false),
+            AccountMeta::new(find_user_address(program_id, user).0, false),
+        ],
+    )
+}
+
+/// `player` is the payer itself unless a session delegate signs for them.
+fn new_game_accounts(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    player: &Pubkey,
+    token: &Pubkey,
+    chainlink_program: &Pubkey,
+    feed: &Pubkey,
+    t: u64,
+) -> Vec<AccountMeta> {
+    let (game, _) = find_game_address(program_id, player);
+
+    vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(find_betting_address(program_id).0, false),
+        AccountMeta::new_readonly(rent::id(), false),
+        AccountMeta::new(find_supported_token_address(program_id, token).0, false),
+        AccountMeta::new(find_user_address(program_id, player).0, false),
+        AccountMeta::new(game, false),
+        AccountMeta::new_readonly(*chainlink_program, false),
+        AccountMeta::new_readonly(*feed, false),
+        AccountMeta::new(get_associated_token_address(player, token), false),
+        AccountMeta::new(get_associated_token_address(&game, token), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*token, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new(find_type_price_address(program_id, t).0, false),
+        AccountMeta::new(find_stats_address(program_id, token).0, false),
+        AccountMeta::new(find_bot_info_address(program_id, player).0, false),
+    ]
+}
+
+#[allow(clippy::too_many_arguments)]
+fn join_game_accounts(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    player: &Pubkey,
+    user_master: &Pubkey,
+    token: &Pubkey,
+    chainlink_program: &Pubkey,
+    feed: &Pubkey,
+    t: u64,
+) -> Vec<AccountMeta> {
+    let (game, _) = find_game_address(program_id, user_master);
+
+    vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(find_betting_address(program_id).0, false),
+        AccountMeta::new_readonly(rent::id(), false),
+        AccountMeta::new(find_supported_token_address(program_id, token).0, false),
+        AccountMeta::new(find_user_address(program_id, player).0, false),
+        AccountMeta::new(find_user_address(program_id, user_master).0, false),
+        AccountMeta::new(game, false),
+        AccountMeta::new_readonly(*chainlink_program, false),
+        AccountMeta::new_readonly(*feed, false),
+        AccountMeta::new(get_associated_token_address(player, token), false),
+        AccountMeta::new(get_associated_token_address(&game, token), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*token, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new(find_type_price_address(program_id, t).0, false),
+        AccountMeta::new(find_stats_address(program_id, token).0, false),
+        AccountMeta::new(find_bot_info_address(program_id, player).0, false),
+    ]
+}
+
+fn session_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    instruction: BettingInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(rent::id(), false),
+            AccountMeta::new_readonly(find_user_address(program_id, payer).0, false),
+            AccountMeta::new(find_session_address(program_id, payer).0, false),
+        ],
+    )
+}
+
+fn profile_instruction(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    instruction: BettingInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(find_user_address(program_id, payer).0, false),
+        ],
+    )
 }
@@ -1,7 +1,10 @@
 pub mod consts;
+pub mod cpi;
 pub mod entrypoint;
 pub mod error;
+pub mod events;
 pub mod instruction;
+pub mod pda;
 pub mod processor;
 pub mod state;
 
@@ -0,0 +1,68 @@
+//! Addresses of the program's accounts, derived the same way the processors check them.
+
+use crate::consts::{
+    BETTING, BOT, BOT_REGISTRY, CONFIG, GAME, REFERRAL, REFERRAL_CODE, ROLE, SESSION, STATS,
+    TYPE_PRICE, USER, WHITELIST,
+};
+use solana_program::pubkey::Pubkey;
+
+pub fn find_betting_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BETTING], program_id)
+}
+
+pub fn find_supported_token_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WHITELIST, &mint.to_bytes()], program_id)
+}
+
+pub fn find_user_address(program_id: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER, &wallet.to_bytes()], program_id)
+}
+
+/// The game opened by `gamer1`.
+pub fn find_game_address(program_id: &Pubkey, gamer1: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME, &gamer1.to_bytes()], program_id)
+}
+
+pub fn find_type_price_address(program_id: &Pubkey, t: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TYPE_PRICE, t.to_string().as_bytes()], program_id)
+}
+
+pub fn find_stats_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STATS, &mint.to_bytes()], program_id)
+}
+
+pub fn find_referral_reward_address(
+    program_id: &Pubkey,
+    referrer: &Pubkey,
+    mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFERRAL, &referrer.to_bytes(), &mint.to_bytes()],
+        program_id,
+    )
+}
+
+pub fn find_referral_code_address(program_id: &Pubkey, code: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRAL_CODE, code.as_bytes()], program_id)
+}
+
+pub fn find_role_address(program_id: &Pubkey, address: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROLE, &address.to_bytes()], program_id)
+}
+
+pub fn find_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG], program_id)
+}
+
+pub fn find_bot_info_address(program_id: &Pubkey, bot: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOT, &bot.to_bytes()], program_id)
+}
+
+pub fn find_bot_registry_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOT_REGISTRY], program_id)
+}
+
+/// The session through which a delegate bets for `user`.
+pub fn find_session_address(program_id: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SESSION, &user.to_bytes()], program_id)
+}
@@ -1,7 +1,16 @@
-use crate::consts::{ADMIN, USER};
+use crate::consts::{
+    BETTING, BOT, BOT_MANAGER, BOT_REGISTRY, DISCRIMINATOR_ACCOUNT_VERSION, RESERVED_SPACE, USER,
+};
 use crate::error::ContractError;
-use crate::state::structs::User;
+use crate::events::BettingEvent;
+use crate::processor::require;
+use crate::processor::roles::check_permission;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::helpers::USER_LEN;
+use crate::state::pod::{PodBettingInfo, PodUser};
+use crate::state::structs::{AccountType, BotInfo, BotRegistry};
 use borsh::BorshSerialize;
+use bytemuck::Zeroable;
 use solana_program::account_info::{next_account_info, AccountInfo};
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program::{invoke, invoke_signed};
@@ -12,36 +21,48 @@ use solana_program::system_instruction;
 use solana_program::sysvar::Sysvar;
 use std::convert::TryInto;
 
-pub fn add_bot(accounts: &[AccountInfo], program_id: &Pubkey, bot: Pubkey) -> ProgramResult {
+/// Registers a bot, or updates the limits of an already registered one. Fails for the address
+/// of a registered player.
+pub fn add_bot(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    bot: Pubkey,
+    max_games: u64,
+    max_type_price: u64,
+    daily_volume_limit: u64,
+) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
     let rent = &Rent::from_account_info(accounts.rent_info)?;
 
-    let admin = ADMIN.parse::<Pubkey>().unwrap();
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    if *accounts.payer.key != admin || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
-    }
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        BOT_MANAGER,
+    )?;
 
-    let (data_address, data_address_bump) =
-        Pubkey::find_program_address(&[USER, &bot.to_bytes()], program_id);
+    if accounts.bot.owner != program_id {
+        let (data_address, data_address_bump) =
+            Pubkey::find_program_address(&[USER, &bot.to_bytes()], program_id);
 
-    if *accounts.bot.key != data_address {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+        if *accounts.bot.key != data_address {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
 
-    let user = User {
-        address: bot,
-        referrer: Pubkey::default(),
-        in_game: false,
-        support_bots: false,
-        is_bot: true,
-        turnover: 0,
-        password: "".to_string(),
-    };
+        let user = PodUser {
+            discriminator: AccountType::User.discriminator(),
+            version: DISCRIMINATOR_ACCOUNT_VERSION,
+            bump: data_address_bump,
+            is_bot: true.into(),
+            address: bot,
+            ..PodUser::zeroed()
+        };
 
-    if accounts.bot.owner != program_id {
-        let size = (user.try_to_vec()?).len();
+        let size = USER_LEN;
 
         let required_lamports = rent
             .minimum_balance(size)
@@ -68,9 +89,165 @@ pub fn add_bot(accounts: &[AccountInfo], program_id: &Pubkey, bot: Pubkey) -> Pr
             &[accounts.bot.clone(), accounts.system_program.clone()],
             &[&[USER, &bot.to_bytes(), &[data_address_bump]]],
         )?;
+
+        PodAccount::init(accounts.bot, user)?;
+    } else {
+        // a registered player can't be turned into a bot
+        let user_info =
+            PodAccount::<PodUser>::load(accounts.bot, program_id, &[USER, &bot.to_bytes()])?;
+
+        require(user_info.is_bot.get(), ContractError::NotABot)?;
     }
 
-    user.serialize(&mut &mut accounts.bot.data.borrow_mut()[..])?;
+    let bot_info = if accounts.bot_info.owner != program_id {
+        let (bot_info_pda, bot_info_bump) =
+            Pubkey::find_program_address(&[BOT, &bot.to_bytes()], program_id);
+
+        if *accounts.bot_info.key != bot_info_pda {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
+
+        let bot_info = BotInfo {
+            discriminator: AccountType::BotInfo.discriminator(),
+            version: DISCRIMINATOR_ACCOUNT_VERSION,
+            bot,
+            suspended: false,
+            max_games,
+            max_type_price,
+            daily_volume_limit,
+            day: 0,
+            daily_volume: 0,
+            bump: bot_info_bump,
+            reserved: [0; RESERVED_SPACE - 1],
+        };
+
+        let size = (bot_info.try_to_vec()?).len() as u64;
+
+        let required_lamports = rent
+            .minimum_balance(size as usize)
+            .max(1)
+            .saturating_sub(accounts.bot_info.lamports());
+
+        invoke(
+            &system_instruction::transfer(accounts.payer.key, &bot_info_pda, required_lamports),
+            &[
+                accounts.payer.clone(),
+                accounts.bot_info.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+
+        invoke_signed(
+            &system_instruction::allocate(&bot_info_pda, size),
+            &[accounts.bot_info.clone(), accounts.system_program.clone()],
+            &[&[BOT, &bot.to_bytes(), &[bot_info_bump]]],
+        )?;
+
+        invoke_signed(
+            &system_instruction::assign(&bot_info_pda, program_id),
+            &[accounts.bot_info.clone(), accounts.system_program.clone()],
+            &[&[BOT, &bot.to_bytes(), &[bot_info_bump]]],
+        )?;
+
+        bot_info
+    } else {
+        BotInfo {
+            max_games,
+            max_type_price,
+            daily_volume_limit,
+            ..ProgramAccount::<BotInfo>::load(
+                accounts.bot_info,
+                program_id,
+                &[BOT, &bot.to_bytes()],
+            )?
+            .data
+        }
+    };
+    bot_info.serialize(&mut &mut accounts.bot_info.data.borrow_mut()[..])?;
+
+    let mut registry = if accounts.registry.owner != program_id {
+        let (registry_pda, registry_bump) =
+            Pubkey::find_program_address(&[BOT_REGISTRY], program_id);
+
+        if *accounts.registry.key != registry_pda {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
+
+        let registry = BotRegistry {
+            discriminator: AccountType::BotRegistry.discriminator(),
+            version: DISCRIMINATOR_ACCOUNT_VERSION,
+            bots: vec![],
+            bump: registry_bump,
+            reserved: [0; RESERVED_SPACE - 1],
+        };
+
+        let size = (registry.try_to_vec()?).len() as u64;
+
+        let required_lamports = rent
+            .minimum_balance(size as usize)
+            .max(1)
+            .saturating_sub(accounts.registry.lamports());
+
+        invoke(
+            &system_instruction::transfer(accounts.payer.key, &registry_pda, required_lamports),
+            &[
+                accounts.payer.clone(),
+                accounts.registry.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+
+        invoke_signed(
+            &system_instruction::allocate(&registry_pda, size),
+            &[accounts.registry.clone(), accounts.system_program.clone()],
+            &[&[BOT_REGISTRY, &[registry_bump]]],
+        )?;
+
+        invoke_signed(
+            &system_instruction::assign(&registry_pda, program_id),
+            &[accounts.registry.clone(), accounts.system_program.clone()],
+            &[&[BOT_REGISTRY, &[registry_bump]]],
+        )?;
+
+        registry
+    } else {
+        ProgramAccount::<BotRegistry>::load(accounts.registry, program_id, &[BOT_REGISTRY])?.data
+    };
+
+    if !registry.bots.contains(&bot) {
+        registry.bots.push(bot);
+
+        let size = (registry.try_to_vec()?).len();
+
+        let required_lamports = rent
+            .minimum_balance(size)
+            .saturating_sub(accounts.registry.lamports());
+
+        invoke(
+            &system_instruction::transfer(
+                accounts.payer.key,
+                accounts.registry.key,
+                required_lamports,
+            ),
+            &[
+                accounts.payer.clone(),
+                accounts.registry.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+
+        accounts.registry.realloc(size, false)?;
+    }
+
+    registry.serialize(&mut &mut accounts.registry.data.borrow_mut()[..])?;
+
+    BettingEvent::BotUpdated {
+        bot,
+        max_games,
+        max_type_price,
+        daily_volume_limit,
+    }
+    .emit()?;
 
     Ok(())
 }
@@ -79,8 +256,12 @@ pub fn add_bot(accounts: &[AccountInfo], program_id: &Pubkey, bot: Pubkey) -> Pr
 pub struct Accounts<'a, 'b> {
     pub payer: &'a AccountInfo<'b>,
     pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
     pub rent_info: &'a AccountInfo<'b>,
     pub bot: &'a AccountInfo<'b>,
+    pub bot_info: &'a AccountInfo<'b>,
+    pub registry: &'a AccountInfo<'b>,
+    pub role: Option<&'a AccountInfo<'b>>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -91,8 +272,12 @@ impl<'a, 'b> Accounts<'a, 'b> {
         Ok(Accounts {
             payer: next_account_info(acc_iter)?,
             system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
             rent_info: next_account_info(acc_iter)?,
             bot: next_account_info(acc_iter)?,
+            bot_info: next_account_info(acc_iter)?,
+            registry: next_account_info(acc_iter)?,
+            role: acc_iter.next(),
         })
     }
 }
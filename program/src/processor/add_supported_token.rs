@@ -1,6 +1,12 @@
-use crate::consts::{ADMIN, WHITELIST};
+use crate::consts::{
+    BETTING, DISCRIMINATOR_ACCOUNT_VERSION, RESERVED_SPACE, TOKEN_LISTER, WHITELIST,
+};
 use crate::error::ContractError;
-use crate::state::structs::SupportedToken;
+use crate::events::BettingEvent;
+use crate::processor::roles::check_permission;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::PodBettingInfo;
+use crate::state::structs::{AccountType, SupportedToken};
 use borsh::BorshSerialize;
 use solana_program::account_info::{next_account_info, AccountInfo};
 use solana_program::entrypoint::ProgramResult;
@@ -22,21 +28,48 @@ pub fn add_supported_token(
 
     let rent = &Rent::from_account_info(accounts.rent_info)?;
 
-    let admin = ADMIN.parse::<Pubkey>().unwrap();
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        TOKEN_LISTER,
+    )?;
+
+    let (paused, data_address_bump) = if accounts.supported_token.owner == program_id {
+        let supported_token_info = ProgramAccount::<SupportedToken>::load(
+            accounts.supported_token,
+            program_id,
+            &[WHITELIST, &supported_token.to_bytes()],
+        )?;
 
-    if *accounts.payer.key != admin || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
-    }
+        (supported_token_info.paused, supported_token_info.bump)
+    } else {
+        let (data_address, data_address_bump) =
+            Pubkey::find_program_address(&[WHITELIST, &supported_token.to_bytes()], program_id);
 
-    let (data_address, data_address_bump) =
-        Pubkey::find_program_address(&[WHITELIST, &supported_token.to_bytes()], program_id);
+        if *accounts.supported_token.key != data_address {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
 
-    if *accounts.supported_token.key != data_address {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+        (false, data_address_bump)
+    };
+
+    let supported_token_info = SupportedToken {
+        discriminator: AccountType::SupportedToken.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        mint: supported_token,
+        feed,
+        is_stablecoin,
+        paused,
+        bump: data_address_bump,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
 
     if accounts.supported_token.owner != program_id {
-        let size: u64 = 32 + 32 + 1;
+        let size = (supported_token_info.try_to_vec()?).len() as u64;
 
         let required_lamports = rent
             .minimum_balance(size as usize)
@@ -44,7 +77,11 @@ pub fn add_supported_token(
             .saturating_sub(accounts.supported_token.lamports());
 
         invoke(
-            &system_instruction::transfer(accounts.payer.key, &data_address, required_lamports),
+            &system_instruction::transfer(
+                accounts.payer.key,
+                accounts.supported_token.key,
+                required_lamports,
+            ),
             &[
                 accounts.payer.clone(),
                 accounts.supported_token.clone(),
@@ -53,7 +90,7 @@ pub fn add_supported_token(
         )?;
 
         invoke_signed(
-            &system_instruction::allocate(&data_address, size),
+            &system_instruction::allocate(accounts.supported_token.key, size),
             &[
                 accounts.supported_token.clone(),
                 accounts.system_program.clone(),
@@ -62,7 +99,7 @@ pub fn add_supported_token(
         )?;
 
         invoke_signed(
-            &system_instruction::assign(&data_address, program_id),
+            &system_instruction::assign(accounts.supported_token.key, program_id),
             &[
                 accounts.supported_token.clone(),
                 accounts.system_program.clone(),
@@ -71,12 +108,14 @@ pub fn add_supported_token(
         )?;
     }
 
-    let supported_token = SupportedToken {
+    supported_token_info.serialize(&mut &mut accounts.supported_token.data.borrow_mut()[..])?;
+
+    BettingEvent::TokenListed {
         mint: supported_token,
         feed,
         is_stablecoin,
-    };
-    supported_token.serialize(&mut &mut accounts.supported_token.data.borrow_mut()[..])?;
+    }
+    .emit()?;
 
     Ok(())
 }
@@ -85,8 +124,10 @@ pub fn add_supported_token(
 pub struct Accounts<'a, 'b> {
     pub payer: &'a AccountInfo<'b>,
     pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
     pub rent_info: &'a AccountInfo<'b>,
     pub supported_token: &'a AccountInfo<'b>,
+    pub role: Option<&'a AccountInfo<'b>>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -97,8 +138,10 @@ impl<'a, 'b> Accounts<'a, 'b> {
         Ok(Accounts {
             payer: next_account_info(acc_iter)?,
             system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
             rent_info: next_account_info(acc_iter)?,
             supported_token: next_account_info(acc_iter)?,
+            role: acc_iter.next(),
         })
     }
 }
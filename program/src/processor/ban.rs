@@ -0,0 +1,103 @@
+use crate::consts::{BETTING, USER};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::require;
+use crate::state::accounts::PodAccount;
+use crate::state::pod::{PodBettingInfo, PodUser};
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::clock::Clock;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
+
+/// Bans `user` for `reason`. An `expiry` of 0 bans the user until `UnbanUser`.
+pub fn ban_user(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    user: Pubkey,
+    reason: u8,
+    expiry: u64,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let mut user_info = get_moderated_user(&accounts, program_id, &user)?;
+
+    user_info.banned = true.into();
+    user_info.ban_reason = reason;
+    user_info.ban_expiry = expiry;
+
+    BettingEvent::UserBanned {
+        user,
+        reason,
+        expiry,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+pub fn unban_user(accounts: &[AccountInfo], program_id: &Pubkey, user: Pubkey) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let mut user_info = get_moderated_user(&accounts, program_id, &user)?;
+
+    user_info.banned = false.into();
+    user_info.ban_reason = 0;
+    user_info.ban_expiry = 0;
+
+    BettingEvent::UserUnbanned { user }.emit()?;
+
+    Ok(())
+}
+
+/// A ban with an expiry in the past no longer applies.
+pub fn is_banned(user_info: &PodUser) -> Result<bool, ProgramError> {
+    if !user_info.banned.get() {
+        return Ok(false);
+    }
+
+    let clock = Clock::get()?;
+
+    Ok(user_info.ban_expiry == 0 || (clock.unix_timestamp as u64) < user_info.ban_expiry)
+}
+
+pub fn check_not_banned(user_info: &PodUser) -> ProgramResult {
+    require(!is_banned(user_info)?, ContractError::UserBanned)
+}
+
+fn get_moderated_user<'a, 'b>(
+    accounts: &Accounts<'a, 'b>,
+    program_id: &Pubkey,
+    user: &Pubkey,
+) -> Result<PodAccount<'a, 'b, PodUser>, ProgramError> {
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    if *accounts.payer.key != betting_info.admin || !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    PodAccount::load(accounts.user, program_id, &[USER, &user.to_bytes()])
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub user: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            user: next_account_info(acc_iter)?,
+        })
+    }
+}
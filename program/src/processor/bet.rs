@@ -1,11 +1,20 @@
-use crate::consts::{BETTING, GAME, PRECISION, TYPE_PRICE, USER, WHITELIST};
+use crate::consts::{
+    ALL_PAYOUTS, BETTING, DISCRIMINATOR_ACCOUNT_VERSION, GAME, PAUSE_NEW_GAME, PRECISION, STATS,
+    TYPE_PRICE, USER, WHITELIST,
+};
 use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::ban::check_not_banned;
+use crate::processor::bots::check_bot_limits;
+use crate::processor::limits::check_limits;
 use crate::processor::require;
-use crate::state::helpers::{
-    get_betting_info, get_supported_token_info, get_type_price_info, get_user_info,
-};
-use crate::state::structs::Game;
-use borsh::BorshSerialize;
+use crate::processor::sessions::use_session;
+use crate::processor::token_stats::create_token_stats;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::helpers::{get_account_version, GAME_LEN};
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use crate::state::structs::{AccountType, SupportedToken, TokenStats, TypePrice};
+use bytemuck::Zeroable;
 use chainlink_solana;
 use num_traits::ToPrimitive;
 use solana_program::account_info::{next_account_info, AccountInfo};
@@ -18,11 +27,14 @@ use solana_program::rent::Rent;
 use solana_program::system_instruction;
 use solana_program::sysvar::Sysvar;
 
+/// Opens a game for the payer or, when `session_user` is set, for that user through the session
+/// the payer holds.
 pub fn bet(
     accounts: &[AccountInfo],
     program_id: &Pubkey,
     t: u64,
     support_bot: bool,
+    session_user: Option<Pubkey>,
 ) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
@@ -34,74 +46,104 @@ pub fn bet(
         return Err(ContractError::UnauthorisedAccess.into());
     }
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let type_price_info = ProgramAccount::<TypePrice>::load(
+        accounts.type_price,
+        program_id,
+        &[TYPE_PRICE, t.to_string().as_bytes()],
+    )?;
 
-    let (type_price, _) =
-        Pubkey::find_program_address(&[TYPE_PRICE, t.to_string().as_bytes()], program_id);
+    require(!type_price_info.paused, ContractError::TypePaused)?;
 
-    if *accounts.type_price.key != type_price {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let value = type_price_info.price;
 
-    let type_price_info = get_type_price_info(&accounts.type_price.data.borrow())?;
+    let player = match session_user {
+        Some(user) => {
+            use_session(
+                accounts.session,
+                program_id,
+                accounts.payer.key,
+                &user,
+                value,
+            )?;
+            user
+        }
+        None => *accounts.payer.key,
+    };
 
-    let value = type_price_info.price;
+    let supported_token_info = ProgramAccount::<SupportedToken>::load(
+        accounts.supported_token,
+        program_id,
+        &[WHITELIST, &accounts.token.key.to_bytes()],
+    )?;
 
-    let (token_pda, _) =
-        Pubkey::find_program_address(&[WHITELIST, &accounts.token.key.to_bytes()], program_id);
+    let mut user_info =
+        PodAccount::<PodUser>::load(accounts.user, program_id, &[USER, &player.to_bytes()])?;
 
-    if *accounts.supported_token.key != token_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    require(
+        (user_info.address == player) || (user_info.is_bot.get() == true),
+        ContractError::NotRegistered,
+    )?;
+    check_not_banned(&user_info)?;
+    require(
+        (user_info.in_game.get() == false) || (user_info.is_bot.get() == true),
+        ContractError::AlreadyInGame,
+    )?;
 
-    let (user_pda, _) =
-        Pubkey::find_program_address(&[USER, &accounts.payer.key.to_bytes()], program_id);
+    let game_bump = if accounts.game.owner == program_id {
+        let game_info =
+            PodAccount::<PodGame>::load(accounts.game, program_id, &[GAME, &player.to_bytes()])?;
 
-    if *accounts.user.key != user_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+        require(game_info.closed.get(), ContractError::AlreadyInGame)?;
+        require(
+            game_info.winner == 0 || game_info.distributed == ALL_PAYOUTS,
+            ContractError::PayoutsPending,
+        )?;
 
-    let (game_pda, _) =
-        Pubkey::find_program_address(&[GAME, &accounts.payer.key.to_bytes()], program_id);
+        game_info.bump
+    } else {
+        let (game_pda, game_bump) =
+            Pubkey::find_program_address(&[GAME, &player.to_bytes()], program_id);
 
-    if *accounts.game.key != game_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+        if *accounts.game.key != game_pda {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
 
-    let mut user_info = get_user_info(&accounts.user.data.borrow())?;
+        game_bump
+    };
+
+    require(!betting_info.shutdown.get(), ContractError::Shutdown)?;
 
     require(
-        (&user_info.address == accounts.payer.key) || (user_info.is_bot == true),
-        "register first",
-    )?;
-    require(
-        (user_info.in_game == false) || (user_info.is_bot == true),
-        "already in game",
+        betting_info.pause_flags & PAUSE_NEW_GAME == 0,
+        ContractError::NewGamesPaused,
     )?;
 
-    let betting_info = get_betting_info(&accounts.pda.data.borrow())?;
-
-    require(betting_info.accept_bets, "bets locked")?;
-
-    let supported_token_info = get_supported_token_info(&accounts.supported_token.data.borrow())?;
+    // the session transfer passes the betting PDA on
+    let betting_bump = betting_info.bump;
+    drop(betting_info);
 
     require(
         supported_token_info.mint == *accounts.token.key,
-        "Token is not supported",
+        ContractError::UnsupportedToken,
     )?;
+    require(!supported_token_info.paused, ContractError::TokenPaused)?;
 
     require(
         supported_token_info.feed == *accounts.feed_account.key,
-        "Wrong feed for this token",
+        ContractError::InvalidFeed,
     )?;
 
-    user_info.support_bots = support_bot;
-    user_info.in_game = true;
-    user_info.serialize(&mut &mut accounts.user.data.borrow_mut()[..])?;
+    if user_info.is_bot.get() {
+        check_bot_limits(accounts.bot_info, program_id, &user_info, value)?;
+    }
+
+    check_limits(&mut user_info, value)?;
+
+    user_info.support_bots = support_bot.into();
+    user_info.in_game = true.into();
+    user_info.active_games += 1;
 
     let convert_value: i128 = chainlink_solana::latest_round_data(
         accounts.chainlink_program.clone(),
@@ -111,16 +153,18 @@ pub fn bet(
 
     let answer = convert_value.to_u64().unwrap();
 
-    if &spl_associated_token_account::get_associated_token_address(
-        accounts.payer.key,
-        accounts.token.key,
-    ) != accounts.source.key
+    let amount = value * PRECISION * PRECISION / answer;
+
+    if spl_associated_token_account::get_associated_token_address(&player, accounts.token.key)
+        != *accounts.source.key
     {
         return Err(ContractError::InvalidInstructionData.into());
     }
 
-    if &spl_associated_token_account::get_associated_token_address(&game_pda, accounts.token.key)
-        != accounts.destination.key
+    if &spl_associated_token_account::get_associated_token_address(
+        accounts.game.key,
+        accounts.token.key,
+    ) != accounts.destination.key
     {
         return Err(ContractError::InvalidInstructionData.into());
     }
@@ -145,36 +189,76 @@ pub fn bet(
         )?;
     }
 
-    invoke(
-        &spl_token::instruction::transfer(
-            accounts.token_program.key,
-            accounts.source.key,
-            accounts.destination.key,
-            accounts.payer.key,
-            &[],
-            value * PRECISION * PRECISION / answer,
-        )?,
-        &[
-            accounts.source.clone(),
-            accounts.destination.clone(),
-            accounts.payer.clone(),
-            accounts.token_program.clone(),
-        ],
+    if session_user.is_some() {
+        // the player approved the betting PDA as the delegate of their token account
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                accounts.token_program.key,
+                accounts.source.key,
+                accounts.destination.key,
+                accounts.pda.key,
+                &[],
+                amount,
+            )?,
+            &[
+                accounts.source.clone(),
+                accounts.destination.clone(),
+                accounts.pda.clone(),
+                accounts.token_program.clone(),
+            ],
+            &[&[BETTING, &[betting_bump]]],
+        )?;
+    } else {
+        invoke(
+            &spl_token::instruction::transfer(
+                accounts.token_program.key,
+                accounts.source.key,
+                accounts.destination.key,
+                accounts.payer.key,
+                &[],
+                amount,
+            )?,
+            &[
+                accounts.source.clone(),
+                accounts.destination.clone(),
+                accounts.payer.clone(),
+                accounts.token_program.clone(),
+            ],
+        )?;
+    }
+
+    let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+    create_token_stats(
+        accounts.payer,
+        accounts.system_program,
+        accounts.stats,
+        rent,
+        program_id,
+        accounts.token.key,
     )?;
 
-    new_game(
-        accounts,
+    let mut stats_info = ProgramAccount::<TokenStats>::load(
+        accounts.stats,
         program_id,
-        value * PRECISION * PRECISION / answer,
-        value,
+        &[STATS, &accounts.token.key.to_bytes()],
     )?;
 
+    stats_info.staked_volume += amount;
+    stats_info.save()?;
+
+    new_game(accounts, program_id, &player, game_bump, amount, value)?;
+
     Ok(())
 }
 
+/// Creates the game of `player`; the payer funds the account. `game_bump` is the bump the caller
+/// checked `accounts.game` with.
 pub fn new_game(
     accounts: Accounts,
     program_id: &Pubkey,
+    player: &Pubkey,
+    game_bump: u8,
     amount: u64,
     type_price: u64,
 ) -> ProgramResult {
@@ -182,15 +266,22 @@ pub fn new_game(
 
     let rent = &Rent::from_account_info(accounts.rent_info)?;
 
-    let (game_pda, game_bump_seed) =
-        Pubkey::find_program_address(&[GAME, &accounts.payer.key.to_bytes()], program_id);
+    let game_pda = *accounts.game.key;
 
-    if accounts.game.key != &game_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let game_info = PodGame {
+        discriminator: AccountType::Game.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        bump: game_bump,
+        amount1: amount,
+        latest_bet: clock.unix_timestamp as u64,
+        type_price,
+        gamer1: *player,
+        token1: *accounts.token.key,
+        ..PodGame::zeroed()
+    };
 
     if accounts.game.owner != program_id {
-        let size: u64 = 32 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 8;
+        let size = GAME_LEN as u64;
 
         let required_lamports = rent
             .minimum_balance(size as usize)
@@ -209,28 +300,32 @@ pub fn new_game(
         invoke_signed(
             &system_instruction::allocate(&game_pda, size),
             &[accounts.game.clone(), accounts.system_program.clone()],
-            &[&[GAME, &accounts.payer.key.to_bytes(), &[game_bump_seed]]],
+            &[&[GAME, &player.to_bytes(), &[game_bump]]],
         )?;
 
         invoke_signed(
             &system_instruction::assign(&game_pda, program_id),
             &[accounts.game.clone(), accounts.system_program.clone()],
-            &[&[GAME, &accounts.payer.key.to_bytes(), &[game_bump_seed]]],
+            &[&[GAME, &player.to_bytes(), &[game_bump]]],
+        )?;
+    } else {
+        require(
+            get_account_version(&accounts.game.data.borrow(), AccountType::Game)?
+                == DISCRIMINATOR_ACCOUNT_VERSION,
+            ContractError::MigrationRequired,
         )?;
     }
 
-    let game_info = Game {
-        gamer1: *accounts.payer.key,
-        gamer2: Pubkey::default(),
-        token1: *accounts.token.key,
-        token2: Pubkey::default(),
-        amount1: amount,
-        amount2: 0,
-        latest_bet: clock.unix_timestamp as u64,
-        closed: false,
+    PodAccount::init(accounts.game, game_info)?;
+
+    BettingEvent::GameCreated {
+        game: *accounts.game.key,
+        gamer: *player,
+        token: *accounts.token.key,
+        amount,
         type_price,
-    };
-    game_info.serialize(&mut &mut accounts.game.data.borrow_mut()[..])?;
+    }
+    .emit()?;
 
     Ok(())
 }
@@ -252,6 +347,10 @@ pub struct Accounts<'a, 'b> {
     pub token: &'a AccountInfo<'b>,
     pub token_assoc: &'a AccountInfo<'b>,
     pub type_price: &'a AccountInfo<'b>,
+    pub stats: &'a AccountInfo<'b>,
+    pub bot_info: Option<&'a AccountInfo<'b>>,
+    /// Only read by `SessionNewGame`.
+    pub session: Option<&'a AccountInfo<'b>>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -275,6 +374,9 @@ impl<'a, 'b> Accounts<'a, 'b> {
             token: next_account_info(acc_iter)?,
             token_assoc: next_account_info(acc_iter)?,
             type_price: next_account_info(acc_iter)?,
+            stats: next_account_info(acc_iter)?,
+            bot_info: acc_iter.next(),
+            session: acc_iter.next(),
         })
     }
 }
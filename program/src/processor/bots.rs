@@ -0,0 +1,187 @@
+use crate::consts::{BETTING, BOT, BOT_MANAGER, BOT_REGISTRY, SECONDS_IN_THE_DAY, USER};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::require;
+use crate::processor::roles::check_permission;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::{PodBettingInfo, PodUser};
+use crate::state::structs::{BotInfo, BotRegistry};
+use borsh::BorshSerialize;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::clock::Clock;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+
+pub fn suspend_bot(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    bot: Pubkey,
+    suspended: bool,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    check_bot_manager(&accounts, program_id)?;
+
+    let mut bot_info =
+        ProgramAccount::<BotInfo>::load(accounts.bot_info, program_id, &[BOT, &bot.to_bytes()])?;
+
+    bot_info.suspended = suspended;
+    bot_info.save()?;
+
+    BettingEvent::BotSuspended { bot, suspended }.emit()?;
+
+    Ok(())
+}
+
+/// Closes the bot's accounts and drops it from the registry. The bot must not be in any game.
+pub fn remove_bot(accounts: &[AccountInfo], program_id: &Pubkey, bot: Pubkey) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    check_bot_manager(&accounts, program_id)?;
+
+    let user_info =
+        PodAccount::<PodUser>::load(accounts.bot, program_id, &[USER, &bot.to_bytes()])?;
+
+    require(user_info.is_bot.get(), ContractError::NotABot)?;
+    require(user_info.active_games == 0, ContractError::BotInGame)?;
+    drop(user_info);
+
+    ProgramAccount::<BotInfo>::load(accounts.bot_info, program_id, &[BOT, &bot.to_bytes()])?;
+
+    let mut registry =
+        ProgramAccount::<BotRegistry>::load(accounts.registry, program_id, &[BOT_REGISTRY])?;
+
+    close_account(accounts.bot, accounts.payer)?;
+    close_account(accounts.bot_info, accounts.payer)?;
+
+    registry.bots.retain(|address| *address != bot);
+
+    let size = (registry.try_to_vec()?).len();
+
+    accounts.registry.realloc(size, false)?;
+    registry.save()?;
+
+    let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+    let excess = accounts
+        .registry
+        .lamports()
+        .saturating_sub(rent.minimum_balance(size));
+
+    **accounts.registry.lamports.borrow_mut() -= excess;
+    **accounts.payer.lamports.borrow_mut() = accounts
+        .payer
+        .lamports()
+        .checked_add(excess)
+        .ok_or(ContractError::OperationWithOverflow)?;
+
+    BettingEvent::BotRemoved { bot }.emit()?;
+
+    Ok(())
+}
+
+/// Enforces the limits of a bot joining or creating a game of `type_price` and counts it
+/// towards the bot's daily volume.
+pub fn check_bot_limits(
+    bot_info: Option<&AccountInfo>,
+    program_id: &Pubkey,
+    user_info: &PodUser,
+    type_price: u64,
+) -> ProgramResult {
+    let bot_info_account = bot_info.ok_or(ContractError::InvalidInstructionData)?;
+
+    let mut bot_info = ProgramAccount::<BotInfo>::load(
+        bot_info_account,
+        program_id,
+        &[BOT, &user_info.address.to_bytes()],
+    )?;
+
+    require(!bot_info.suspended, ContractError::BotSuspended)?;
+    require(
+        bot_info.max_games == 0 || user_info.active_games < bot_info.max_games,
+        ContractError::BotGameLimit,
+    )?;
+    require(
+        bot_info.max_type_price == 0 || type_price <= bot_info.max_type_price,
+        ContractError::BotStakeLimit,
+    )?;
+
+    let day = Clock::get()?.unix_timestamp as u64 / SECONDS_IN_THE_DAY;
+
+    if bot_info.day != day {
+        bot_info.day = day;
+        bot_info.daily_volume = 0;
+    }
+
+    bot_info.daily_volume += type_price;
+
+    require(
+        bot_info.daily_volume_limit == 0 || bot_info.daily_volume <= bot_info.daily_volume_limit,
+        ContractError::BotVolumeLimit,
+    )?;
+
+    bot_info.save()?;
+
+    Ok(())
+}
+
+/// Moves all lamports of `account` to `destination` and wipes its data; the runtime drops the
+/// account at the end of the transaction.
+pub fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ContractError::OperationWithOverflow)?;
+    **account.lamports.borrow_mut() = 0;
+
+    account.data.borrow_mut().fill(0);
+
+    Ok(())
+}
+
+fn check_bot_manager(accounts: &Accounts, program_id: &Pubkey) -> ProgramResult {
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        BOT_MANAGER,
+    )
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub bot: &'a AccountInfo<'b>,
+    pub bot_info: &'a AccountInfo<'b>,
+    pub registry: &'a AccountInfo<'b>,
+    pub role: Option<&'a AccountInfo<'b>>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            bot: next_account_info(acc_iter)?,
+            bot_info: next_account_info(acc_iter)?,
+            registry: next_account_info(acc_iter)?,
+            role: acc_iter.next(),
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use crate::consts::{BETTING, REFERRAL};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::referral_rewards::create_referral_reward;
+use crate::processor::require;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::PodBettingInfo;
+use crate::state::structs::ReferralReward;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+
+/// Transfers the rewards accrued to the payer in `token` out of the vault. A claim before any
+/// reward only opens the reward PDA at the payer's expense; the referral payout opens it otherwise.
+pub fn claim_referral_rewards(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    if *accounts.token_program.key != spl_token::id() {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    let betting_bump =
+        PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?.bump;
+
+    if accounts.reward.owner != program_id {
+        let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+        return create_referral_reward(
+            accounts.payer,
+            accounts.system_program,
+            accounts.reward,
+            rent,
+            program_id,
+            accounts.payer.key,
+            accounts.token.key,
+        );
+    }
+
+    let mut reward_info = ProgramAccount::<ReferralReward>::load(
+        accounts.reward,
+        program_id,
+        &[
+            REFERRAL,
+            &accounts.payer.key.to_bytes(),
+            &accounts.token.key.to_bytes(),
+        ],
+    )?;
+
+    require(reward_info.balance > 0, ContractError::NothingToClaim)?;
+
+    if &spl_associated_token_account::get_associated_token_address(
+        accounts.pda.key,
+        accounts.token.key,
+    ) != accounts.vault.key
+    {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    if &spl_associated_token_account::get_associated_token_address(
+        accounts.payer.key,
+        accounts.token.key,
+    ) != accounts.destination.key
+    {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    let amount = reward_info.balance;
+
+    reward_info.balance = 0;
+    reward_info.claimed += amount;
+    reward_info.save()?;
+
+    if accounts.destination.owner != accounts.token_program.key {
+        invoke(
+            &spl_associated_token_account::create_associated_token_account(
+                accounts.payer.key,
+                accounts.payer.key,
+                accounts.token.key,
+            ),
+            &[
+                accounts.payer.clone(),
+                accounts.destination.clone(),
+                accounts.payer.clone(),
+                accounts.token.clone(),
+                accounts.system_program.clone(),
+                accounts.token_program.clone(),
+                accounts.rent_info.clone(),
+                accounts.token_assoc.clone(),
+            ],
+        )?;
+    }
+
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            accounts.token_program.key,
+            accounts.vault.key,
+            accounts.destination.key,
+            accounts.pda.key,
+            &[],
+            amount,
+        )?,
+        &[
+            accounts.vault.clone(),
+            accounts.destination.clone(),
+            accounts.pda.clone(),
+            accounts.token_program.clone(),
+        ],
+        &[&[BETTING, &[betting_bump]]],
+    )?;
+
+    BettingEvent::ReferralRewardsClaimed {
+        referrer: *accounts.payer.key,
+        mint: *accounts.token.key,
+        amount,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub reward: &'a AccountInfo<'b>,
+    pub vault: &'a AccountInfo<'b>,
+    pub destination: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+    pub token: &'a AccountInfo<'b>,
+    pub token_assoc: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            reward: next_account_info(acc_iter)?,
+            vault: next_account_info(acc_iter)?,
+            destination: next_account_info(acc_iter)?,
+            token_program: next_account_info(acc_iter)?,
+            token: next_account_info(acc_iter)?,
+            token_assoc: next_account_info(acc_iter)?,
+        })
+    }
+}
@@ -0,0 +1,224 @@
+use crate::consts::{
+    BETTING, CONFIG, DISCRIMINATOR_ACCOUNT_VERSION, FEE_ADMIN, MAX_CLOSE_DELAY, MAX_CONFIG_DELAY,
+    RESERVED_SPACE,
+};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::require;
+use crate::processor::roles::check_permission;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::PodBettingInfo;
+use crate::state::structs::{AccountType, Config, PendingConfig};
+use borsh::BorshSerialize;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::clock::Clock;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::sysvar::Sysvar;
+
+/// Records new fee, delay and referral values; they can be applied once `config_delay` has passed.
+pub fn queue_config_change(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    config: Config,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+    let clock = Clock::get()?;
+
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        FEE_ADMIN,
+    )?;
+
+    require(
+        config.referrer_fee <= 100
+            && config.admin_fee <= 100
+            && config.global_fee <= 100
+            && config.global_fee * 2 <= 100,
+        ContractError::InvalidFees,
+    )?;
+
+    require(
+        config.referral_tiers.iter().all(|tier| *tier <= 100)
+            && config.referral_tiers.iter().sum::<u64>() <= 100,
+        ContractError::InvalidReferralTiers,
+    )?;
+
+    require(
+        config.config_delay <= MAX_CONFIG_DELAY && config.close_delay <= MAX_CLOSE_DELAY,
+        ContractError::InvalidDelays,
+    )?;
+
+    let execute_after = (clock.unix_timestamp as u64)
+        .checked_add(betting_info.config_delay)
+        .ok_or(ContractError::OperationWithOverflow)?;
+
+    let (pending_pda, pending_bump) = Pubkey::find_program_address(&[CONFIG], program_id);
+
+    if *accounts.pending.key != pending_pda {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    let pending_info = PendingConfig {
+        discriminator: AccountType::PendingConfig.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        config,
+        execute_after,
+        bump: pending_bump,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+
+    if accounts.pending.owner != program_id {
+        let size = (pending_info.try_to_vec()?).len() as u64;
+
+        let required_lamports = rent
+            .minimum_balance(size as usize)
+            .max(1)
+            .saturating_sub(accounts.pending.lamports());
+
+        invoke(
+            &system_instruction::transfer(accounts.payer.key, &pending_pda, required_lamports),
+            &[
+                accounts.payer.clone(),
+                accounts.pending.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+
+        invoke_signed(
+            &system_instruction::allocate(&pending_pda, size),
+            &[accounts.pending.clone(), accounts.system_program.clone()],
+            &[&[CONFIG, &[pending_bump]]],
+        )?;
+
+        invoke_signed(
+            &system_instruction::assign(&pending_pda, program_id),
+            &[accounts.pending.clone(), accounts.system_program.clone()],
+            &[&[CONFIG, &[pending_bump]]],
+        )?;
+    } else {
+        let pending_info =
+            ProgramAccount::<PendingConfig>::load(accounts.pending, program_id, &[CONFIG])?;
+
+        require(
+            pending_info.execute_after == 0,
+            ContractError::ConfigChangeAlreadyQueued,
+        )?;
+    }
+
+    pending_info.serialize(&mut &mut accounts.pending.data.borrow_mut()[..])?;
+
+    BettingEvent::ConfigChangeQueued {
+        config: pending_info.config.clone(),
+        execute_after: pending_info.execute_after,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Applies the queued change once its timelock is over. Anyone may execute it.
+pub fn execute_config_change(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let clock = Clock::get()?;
+
+    let mut betting_info =
+        PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    let mut pending_info =
+        ProgramAccount::<PendingConfig>::load(accounts.pending, program_id, &[CONFIG])?;
+
+    require(
+        pending_info.execute_after != 0,
+        ContractError::NoConfigChangeQueued,
+    )?;
+    require(
+        (clock.unix_timestamp as u64) >= pending_info.execute_after,
+        ContractError::ConfigChangeTimelocked,
+    )?;
+
+    let config = pending_info.config.clone();
+
+    betting_info.referrer_fee = config.referrer_fee;
+    betting_info.admin_fee = config.admin_fee;
+    betting_info.global_fee = config.global_fee;
+    betting_info.transaction_fee = config.transaction_fee;
+    betting_info.close_delay = config.close_delay;
+    betting_info.referral_tiers = config.referral_tiers;
+    betting_info.config_delay = config.config_delay;
+
+    pending_info.execute_after = 0;
+    pending_info.save()?;
+
+    BettingEvent::ConfigChanged { config }.emit()?;
+
+    Ok(())
+}
+
+pub fn cancel_config_change(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        FEE_ADMIN,
+    )?;
+
+    let mut pending_info =
+        ProgramAccount::<PendingConfig>::load(accounts.pending, program_id, &[CONFIG])?;
+
+    require(
+        pending_info.execute_after != 0,
+        ContractError::NoConfigChangeQueued,
+    )?;
+
+    pending_info.execute_after = 0;
+    pending_info.save()?;
+
+    BettingEvent::ConfigChangeCancelled.emit()?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub pending: &'a AccountInfo<'b>,
+    pub role: Option<&'a AccountInfo<'b>>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            pending: next_account_info(acc_iter)?,
+            role: acc_iter.next(),
+        })
+    }
+}
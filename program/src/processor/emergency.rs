@@ -0,0 +1,241 @@
+use crate::consts::{BETTING, GAME, USER};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::forced_close_game::{close_game_account, refund, RefundAccounts};
+use crate::processor::require;
+use crate::processor::user_stats::record_cancellation;
+use crate::state::accounts::PodAccount;
+use crate::state::helpers::get_game_info;
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+/// Stops new games, joins and settlements until the admin lifts the shutdown.
+pub fn emergency_shutdown(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    shutdown: bool,
+) -> ProgramResult {
+    let accounts = ShutdownAccounts::new(accounts)?;
+
+    let mut betting_info =
+        PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    if *accounts.payer.key != betting_info.admin || !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    betting_info.shutdown = shutdown.into();
+
+    BettingEvent::EmergencyShutdown { shutdown }.emit()?;
+
+    Ok(())
+}
+
+/// Returns both stakes of an open `game` in full, without fees. Anyone can crank it during a shutdown.
+pub fn emergency_refund(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    game: Pubkey,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    if *accounts.token_program.key != spl_token::id() {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    require(betting_info.shutdown.get(), ContractError::NotShutdown)?;
+
+    if *accounts.game.key != game || accounts.game.owner != program_id {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    // The game is keyed by its creator, so the seeds come from the account itself.
+    let user = get_game_info(&accounts.game.data.borrow())?.gamer1;
+
+    let mut game_info =
+        PodAccount::<PodGame>::load(accounts.game, program_id, &[GAME, &user.to_bytes()])?;
+    let game_bump = game_info.bump;
+
+    require(!game_info.closed.get(), ContractError::GameClosed)?;
+
+    let mut user_info =
+        PodAccount::<PodUser>::load(accounts.user, program_id, &[USER, &user.to_bytes()])?;
+
+    let started = game_info.gamer2 != Pubkey::default();
+
+    if started {
+        let user2 = accounts
+            .user2
+            .ok_or(ContractError::InvalidInstructionData)?;
+
+        let mut user2_info =
+            PodAccount::<PodUser>::load(user2, program_id, &[USER, &game_info.gamer2.to_bytes()])?;
+
+        user2_info.in_game = false.into();
+        user2_info.active_games = user2_info.active_games.saturating_sub(1);
+        record_cancellation(&mut user2_info, 0)?;
+    }
+
+    game_info.closed = true.into();
+
+    user_info.in_game = false.into();
+    user_info.active_games = user_info.active_games.saturating_sub(1);
+    record_cancellation(&mut user_info, 0)?;
+
+    if *accounts.user_wallet.key != user || *accounts.token.key != game_info.token1 {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    let (amount1, amount2, gamer1, gamer2, token2) = (
+        game_info.amount1,
+        game_info.amount2,
+        game_info.gamer1,
+        game_info.gamer2,
+        game_info.token2,
+    );
+
+    // the refunds pass the game on as the authority of its token accounts
+    drop(game_info);
+
+    let refund_accounts = RefundAccounts {
+        payer: accounts.payer,
+        system_program: accounts.system_program,
+        rent_info: accounts.rent_info,
+        game: accounts.game,
+        token_program: accounts.token_program,
+        token_assoc: accounts.token_assoc,
+        user,
+        game_bump,
+    };
+
+    refund(
+        &refund_accounts,
+        accounts.source,
+        accounts.destination,
+        accounts.user_wallet,
+        accounts.token,
+        amount1,
+    )?;
+
+    if started {
+        let user2_wallet = accounts
+            .user2_wallet
+            .ok_or(ContractError::InvalidInstructionData)?;
+        let source1 = accounts
+            .source1
+            .ok_or(ContractError::InvalidInstructionData)?;
+        let destination1 = accounts
+            .destination1
+            .ok_or(ContractError::InvalidInstructionData)?;
+        let token1 = accounts
+            .token1
+            .ok_or(ContractError::InvalidInstructionData)?;
+
+        if *user2_wallet.key != gamer2 || *token1.key != token2 {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
+
+        refund(
+            &refund_accounts,
+            source1,
+            destination1,
+            user2_wallet,
+            token1,
+            amount2,
+        )?;
+
+        if source1.key != accounts.source.key {
+            close_game_account(&refund_accounts, source1, user2_wallet)?;
+        }
+    }
+
+    close_game_account(&refund_accounts, accounts.source, accounts.user_wallet)?;
+
+    BettingEvent::GameCancelled {
+        game: *accounts.game.key,
+        gamer1,
+        amount1,
+        gamer2,
+        amount2,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub struct ShutdownAccounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> ShutdownAccounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<ShutdownAccounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(ShutdownAccounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+        })
+    }
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub user: &'a AccountInfo<'b>,
+    pub user_wallet: &'a AccountInfo<'b>,
+    pub game: &'a AccountInfo<'b>,
+    pub source: &'a AccountInfo<'b>,
+    pub destination: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+    pub token: &'a AccountInfo<'b>,
+    pub token_assoc: &'a AccountInfo<'b>,
+    pub user2: Option<&'a AccountInfo<'b>>,
+    pub user2_wallet: Option<&'a AccountInfo<'b>>,
+    pub source1: Option<&'a AccountInfo<'b>>,
+    pub destination1: Option<&'a AccountInfo<'b>>,
+    pub token1: Option<&'a AccountInfo<'b>>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            user: next_account_info(acc_iter)?,
+            user_wallet: next_account_info(acc_iter)?,
+            game: next_account_info(acc_iter)?,
+            source: next_account_info(acc_iter)?,
+            destination: next_account_info(acc_iter)?,
+            token_program: next_account_info(acc_iter)?,
+            token: next_account_info(acc_iter)?,
+            token_assoc: next_account_info(acc_iter)?,
+            user2: acc_iter.next(),
+            user2_wallet: acc_iter.next(),
+            source1: acc_iter.next(),
+            destination1: acc_iter.next(),
+            token1: acc_iter.next(),
+        })
+    }
+}
@@ -1,8 +1,13 @@
-use crate::consts::{BETTING, GAME, USER, WHITELIST};
+use crate::consts::{BETTING, GAME, RESOLVER, USER, WHITELIST};
 use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::ban::is_banned;
 use crate::processor::require;
-use crate::state::helpers::{get_betting_info, get_game_info, get_user_info};
-use borsh::BorshSerialize;
+use crate::processor::roles::check_permission;
+use crate::processor::user_stats::record_cancellation;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use crate::state::structs::SupportedToken;
 use solana_program::account_info::{next_account_info, AccountInfo};
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program::{invoke, invoke_signed};
@@ -20,77 +25,172 @@ pub fn forced_close(accounts: &[AccountInfo], program_id: &Pubkey, user: Pubkey)
         return Err(ContractError::UnauthorisedAccess.into());
     }
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        RESOLVER,
+    )?;
 
-    let betting_info = get_betting_info(&accounts.pda.data.borrow())?;
+    let mut game_info =
+        PodAccount::<PodGame>::load(accounts.game, program_id, &[GAME, &user.to_bytes()])?;
+    let game_bump = game_info.bump;
 
-    if &betting_info.manager != accounts.payer.key {
-        return Err(ContractError::UnauthorisedAccess.into());
+    require(!game_info.closed.get(), ContractError::GameClosed)?;
+
+    let mut user_info =
+        PodAccount::<PodUser>::load(accounts.user, program_id, &[USER, &user.to_bytes()])?;
+
+    let started = game_info.gamer2 != Pubkey::default();
+
+    // A started game is only refunded when one of its players is banned.
+    if started {
+        let user2 = accounts
+            .user2
+            .ok_or(ContractError::InvalidInstructionData)?;
+
+        let mut user2_info =
+            PodAccount::<PodUser>::load(user2, program_id, &[USER, &game_info.gamer2.to_bytes()])?;
+
+        require(
+            is_banned(&user_info)? || is_banned(&user2_info)?,
+            ContractError::GameAlreadyStarted,
+        )?;
+
+        user2_info.in_game = false.into();
+        user2_info.active_games = user2_info.active_games.saturating_sub(1);
+        record_cancellation(&mut user2_info, 0)?;
     }
 
-    let (game_pda, game_bump) = Pubkey::find_program_address(&[GAME, &user.to_bytes()], program_id);
+    game_info.closed = true.into();
+
+    user_info.in_game = false.into();
+    user_info.active_games = user_info.active_games.saturating_sub(1);
+    record_cancellation(&mut user_info, 0)?;
+
+    ProgramAccount::<SupportedToken>::load(
+        accounts.supported_token,
+        program_id,
+        &[WHITELIST, &accounts.token.key.to_bytes()],
+    )?;
 
-    if *accounts.game.key != game_pda {
+    if *accounts.user_wallet.key != user || *accounts.token.key != game_info.token1 {
         return Err(ContractError::InvalidInstructionData.into());
     }
 
-    let mut game_info = get_game_info(&accounts.game.data.borrow())?;
+    let (amount1, amount2, gamer1, gamer2, token2) = (
+        game_info.amount1,
+        game_info.amount2,
+        game_info.gamer1,
+        game_info.gamer2,
+        game_info.token2,
+    );
 
-    require(!game_info.closed, "Game already closed")?;
-    require(
-        game_info.gamer2 == Pubkey::default(),
-        "Game started already",
-    )?;
+    // the refunds pass the game on as the authority of its token accounts
+    drop(game_info);
 
-    game_info.closed = true;
-    game_info.serialize(&mut &mut accounts.game.data.borrow_mut()[..])?;
+    let refund_accounts = RefundAccounts {
+        payer: accounts.payer,
+        system_program: accounts.system_program,
+        rent_info: accounts.rent_info,
+        game: accounts.game,
+        token_program: accounts.token_program,
+        token_assoc: accounts.token_assoc,
+        user,
+        game_bump,
+    };
 
-    let (user_pda, _) = Pubkey::find_program_address(&[USER, &user.to_bytes()], program_id);
+    refund(
+        &refund_accounts,
+        accounts.source,
+        accounts.destination,
+        accounts.user_wallet,
+        accounts.token,
+        amount1,
+    )?;
 
-    if *accounts.user.key != user_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    if started {
+        let user2_wallet = accounts
+            .user2_wallet
+            .ok_or(ContractError::InvalidInstructionData)?;
+        let source1 = accounts
+            .source1
+            .ok_or(ContractError::InvalidInstructionData)?;
+        let destination1 = accounts
+            .destination1
+            .ok_or(ContractError::InvalidInstructionData)?;
+        let token1 = accounts
+            .token1
+            .ok_or(ContractError::InvalidInstructionData)?;
 
-    let mut user_info = get_user_info(&accounts.user.data.borrow())?;
+        if *user2_wallet.key != gamer2 || *token1.key != token2 {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
 
-    user_info.in_game = false;
-    user_info.serialize(&mut &mut accounts.user.data.borrow_mut()[..])?;
+        refund(
+            &refund_accounts,
+            source1,
+            destination1,
+            user2_wallet,
+            token1,
+            amount2,
+        )?;
 
-    let (token_pda, _) =
-        Pubkey::find_program_address(&[WHITELIST, &accounts.token.key.to_bytes()], program_id);
+        if source1.key != accounts.source.key {
+            close_game_account(&refund_accounts, source1, user2_wallet)?;
+        }
+    }
 
-    if *accounts.supported_token.key != token_pda {
-        return Err(ContractError::InvalidInstructionData.into());
+    close_game_account(&refund_accounts, accounts.source, accounts.user_wallet)?;
+
+    BettingEvent::GameCancelled {
+        game: *accounts.game.key,
+        gamer1,
+        amount1,
+        gamer2,
+        amount2,
     }
+    .emit()?;
 
-    if &spl_associated_token_account::get_associated_token_address(&game_pda, accounts.token.key)
-        != accounts.source.key
+    Ok(())
+}
+
+/// Sends `amount` of `token` from the game's `source` account back to `wallet`.
+pub fn refund<'a, 'b>(
+    accounts: &RefundAccounts<'a, 'b>,
+    source: &'a AccountInfo<'b>,
+    destination: &'a AccountInfo<'b>,
+    wallet: &'a AccountInfo<'b>,
+    token: &'a AccountInfo<'b>,
+    amount: u64,
+) -> ProgramResult {
+    if &spl_associated_token_account::get_associated_token_address(accounts.game.key, token.key)
+        != source.key
     {
         return Err(ContractError::InvalidInstructionData.into());
     }
 
-    if &spl_associated_token_account::get_associated_token_address(&user, accounts.token.key)
-        != accounts.destination.key
+    if &spl_associated_token_account::get_associated_token_address(wallet.key, token.key)
+        != destination.key
     {
         return Err(ContractError::InvalidInstructionData.into());
     }
 
-    if accounts.destination.owner != accounts.token_program.key {
+    if destination.owner != accounts.token_program.key {
         invoke(
             &spl_associated_token_account::create_associated_token_account(
                 accounts.payer.key,
-                &user,
-                accounts.token.key,
+                wallet.key,
+                token.key,
             ),
             &[
                 accounts.payer.clone(),
-                accounts.destination.clone(),
-                accounts.user_wallet.clone(),
-                accounts.token.clone(),
+                destination.clone(),
+                wallet.clone(),
+                token.clone(),
                 accounts.system_program.clone(),
                 accounts.token_program.clone(),
                 accounts.rent_info.clone(),
@@ -102,39 +202,56 @@ pub fn forced_close(accounts: &[AccountInfo], program_id: &Pubkey, user: Pubkey)
     invoke_signed(
         &spl_token::instruction::transfer(
             accounts.token_program.key,
-            accounts.source.key,
-            accounts.destination.key,
+            source.key,
+            destination.key,
             accounts.game.key,
             &[],
-            game_info.amount1,
+            amount,
         )?,
         &[
-            accounts.source.clone(),
-            accounts.destination.clone(),
+            source.clone(),
+            destination.clone(),
             accounts.game.clone(),
             accounts.token_program.clone(),
         ],
-        &[&[GAME, &user.to_bytes(), &[game_bump]]],
-    )?;
+        &[&[GAME, &accounts.user.to_bytes(), &[accounts.game_bump]]],
+    )
+}
 
+pub fn close_game_account<'a, 'b>(
+    accounts: &RefundAccounts<'a, 'b>,
+    source: &'a AccountInfo<'b>,
+    wallet: &'a AccountInfo<'b>,
+) -> ProgramResult {
     invoke_signed(
         &spl_token::instruction::close_account(
             accounts.token_program.key,
-            accounts.source.key,
-            &user,
+            source.key,
+            wallet.key,
             accounts.game.key,
             &[],
         )?,
         &[
-            accounts.source.clone(),
-            accounts.user_wallet.clone(),
+            source.clone(),
+            wallet.clone(),
             accounts.game.clone(),
             accounts.token_program.clone(),
         ],
-        &[&[GAME, &user.to_bytes(), &[game_bump]]],
-    )?;
+        &[&[GAME, &accounts.user.to_bytes(), &[accounts.game_bump]]],
+    )
+}
 
-    Ok(())
+/// The accounts `refund` and `close_game_account` need besides the token accounts, and the
+/// seeds of the game PDA signing for them.
+pub struct RefundAccounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub game: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+    pub token_assoc: &'a AccountInfo<'b>,
+    pub user: Pubkey,
+    pub game_bump: u8,
 }
 
 #[allow(dead_code)]
@@ -152,6 +269,12 @@ pub struct Accounts<'a, 'b> {
     pub token_program: &'a AccountInfo<'b>,
     pub token: &'a AccountInfo<'b>,
     pub token_assoc: &'a AccountInfo<'b>,
+    pub role: Option<&'a AccountInfo<'b>>,
+    pub user2: Option<&'a AccountInfo<'b>>,
+    pub user2_wallet: Option<&'a AccountInfo<'b>>,
+    pub source1: Option<&'a AccountInfo<'b>>,
+    pub destination1: Option<&'a AccountInfo<'b>>,
+    pub token1: Option<&'a AccountInfo<'b>>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -173,6 +296,12 @@ impl<'a, 'b> Accounts<'a, 'b> {
             token_program: next_account_info(acc_iter)?,
             token: next_account_info(acc_iter)?,
             token_assoc: next_account_info(acc_iter)?,
+            role: acc_iter.next(),
+            user2: acc_iter.next(),
+            user2_wallet: acc_iter.next(),
+            source1: acc_iter.next(),
+            destination1: acc_iter.next(),
+            token1: acc_iter.next(),
         })
     }
 }
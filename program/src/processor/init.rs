@@ -1,8 +1,16 @@
-use crate::consts::{ADMIN, BETTING, WHITELIST};
+use crate::consts::{
+    BETTING, DISCRIMINATOR_ACCOUNT_VERSION, RESERVED_SPACE, SECONDS_IN_THE_DAY, WHITELIST,
+};
 use crate::error::ContractError;
-use crate::state::structs::{BettingInfo, SupportedToken};
+use crate::events::BettingEvent;
+use crate::state::accounts::PodAccount;
+use crate::state::helpers::BETTING_INFO_LEN;
+use crate::state::pod::PodBettingInfo;
+use crate::state::structs::{AccountType, SupportedToken};
 use borsh::BorshSerialize;
+use bytemuck::Zeroable;
 use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::bpf_loader_upgradeable;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program::{invoke, invoke_signed};
 use solana_program::program_error::ProgramError;
@@ -14,7 +22,7 @@ use solana_program::sysvar::Sysvar;
 pub fn init(
     accounts: &[AccountInfo],
     program_id: &Pubkey,
-    manager: Pubkey,
+    admin: Pubkey,
     supported_token: Pubkey,
     feed: Pubkey,
     is_stablecoin: bool,
@@ -29,52 +37,66 @@ pub fn init(
         return Err(ContractError::InvalidInstructionData.into());
     }
 
-    let admin = ADMIN.parse::<Pubkey>().unwrap();
-
-    if *accounts.payer.key != admin || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
+    if accounts.pda.owner == program_id {
+        return Err(ProgramError::AccountAlreadyInitialized);
     }
 
-    if accounts.pda.owner != program_id {
-        let size: u64 = 8 + 8 + 8 + 8 + 1 + 8 + 32;
-
-        let required_lamports = rent
-            .minimum_balance(size as usize)
-            .max(1)
-            .saturating_sub(accounts.pda.lamports());
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
 
-        invoke(
-            &system_instruction::transfer(accounts.payer.key, &betting_pda, required_lamports),
-            &[
-                accounts.payer.clone(),
-                accounts.pda.clone(),
-                accounts.system_program.clone(),
-            ],
-        )?;
+    if *accounts.program_data.key != program_data {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
 
-        invoke_signed(
-            &system_instruction::allocate(&betting_pda, size),
-            &[accounts.pda.clone(), accounts.system_program.clone()],
-            &[&[BETTING, &[betting_bump_seed]]],
-        )?;
+    let upgrade_authority = get_upgrade_authority(&accounts.program_data.data.borrow())?;
 
-        invoke_signed(
-            &system_instruction::assign(&betting_pda, program_id),
-            &[accounts.pda.clone(), accounts.system_program.clone()],
-            &[&[BETTING, &[betting_bump_seed]]],
-        )?;
+    if upgrade_authority != Some(*accounts.payer.key) || !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
     }
 
-    let betting_info = BettingInfo {
+    let betting_info = PodBettingInfo {
+        discriminator: AccountType::BettingInfo.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        bump: betting_bump_seed,
         referrer_fee: 50,
         admin_fee: 50,
         global_fee: 10,
-        transaction_fee: 0,
-        accept_bets: true,
         close_delay: 300,
-        manager,
+        referral_tiers: [100, 0, 0],
+        config_delay: SECONDS_IN_THE_DAY,
+        admin,
+        ..PodBettingInfo::zeroed()
     };
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
+
+    let size = BETTING_INFO_LEN as u64;
+
+    let required_lamports = rent
+        .minimum_balance(size as usize)
+        .max(1)
+        .saturating_sub(accounts.pda.lamports());
+
+    invoke(
+        &system_instruction::transfer(accounts.payer.key, &betting_pda, required_lamports),
+        &[
+            accounts.payer.clone(),
+            accounts.pda.clone(),
+            accounts.system_program.clone(),
+        ],
+    )?;
+
+    invoke_signed(
+        &system_instruction::allocate(&betting_pda, size),
+        &[accounts.pda.clone(), accounts.system_program.clone()],
+        &[&[BETTING, &[betting_bump_seed]]],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(&betting_pda, program_id),
+        &[accounts.pda.clone(), accounts.system_program.clone()],
+        &[&[BETTING, &[betting_bump_seed]]],
+    )?;
+
+    PodAccount::init(accounts.pda, betting_info)?;
 
     let (data_address, data_address_bump) =
         Pubkey::find_program_address(&[WHITELIST, &supported_token.to_bytes()], program_id);
@@ -83,8 +105,19 @@ pub fn init(
         return Err(ContractError::InvalidInstructionData.into());
     }
 
+    let supported_token_info = SupportedToken {
+        discriminator: AccountType::SupportedToken.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        mint: supported_token,
+        feed,
+        is_stablecoin,
+        paused: false,
+        bump: data_address_bump,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+
     if accounts.supported_token.owner != program_id {
-        let size: u64 = 32 + 32 + 1;
+        let size = (supported_token_info.try_to_vec()?).len() as u64;
 
         let required_lamports = rent
             .minimum_balance(size as usize)
@@ -119,16 +152,34 @@ pub fn init(
         )?;
     }
 
-    let supported_token = SupportedToken {
+    supported_token_info.serialize(&mut &mut accounts.supported_token.data.borrow_mut()[..])?;
+
+    BettingEvent::Initialized { admin }.emit()?;
+
+    BettingEvent::TokenListed {
         mint: supported_token,
         feed,
         is_stablecoin,
-    };
-    supported_token.serialize(&mut &mut accounts.supported_token.data.borrow_mut()[..])?;
+    }
+    .emit()?;
 
     Ok(())
 }
 
+/// Reads the upgrade authority out of the bincode-encoded `UpgradeableLoaderState::ProgramData`
+/// header: a u32 variant tag, the deployment slot and an optional authority.
+fn get_upgrade_authority(data: &[u8]) -> Result<Option<Pubkey>, ProgramError> {
+    if data.len() < 4 + 8 + 1 || data[0..4] != 3u32.to_le_bytes() {
+        return Err(ContractError::DeserializeError.into());
+    }
+
+    match data[12] {
+        0 => Ok(None),
+        1 if data.len() >= 4 + 8 + 1 + 32 => Ok(Some(Pubkey::new(&data[13..45]))),
+        _ => Err(ContractError::DeserializeError.into()),
+    }
+}
+
 #[allow(dead_code)]
 pub struct Accounts<'a, 'b> {
     pub payer: &'a AccountInfo<'b>,
@@ -136,6 +187,7 @@ pub struct Accounts<'a, 'b> {
     pub pda: &'a AccountInfo<'b>,
     pub rent_info: &'a AccountInfo<'b>,
     pub supported_token: &'a AccountInfo<'b>,
+    pub program_data: &'a AccountInfo<'b>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -149,6 +201,7 @@ impl<'a, 'b> Accounts<'a, 'b> {
             pda: next_account_info(acc_iter)?,
             rent_info: next_account_info(acc_iter)?,
             supported_token: next_account_info(acc_iter)?,
+            program_data: next_account_info(acc_iter)?,
         })
     }
 }
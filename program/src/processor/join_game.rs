@@ -1,25 +1,36 @@
-use crate::consts::{BETTING, GAME, PRECISION, TYPE_PRICE, USER, WHITELIST};
+use crate::consts::{
+    BETTING, GAME, PAUSE_JOIN_GAME, PRECISION, STATS, TYPE_PRICE, USER, WHITELIST,
+};
 use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::ban::check_not_banned;
+use crate::processor::bots::check_bot_limits;
+use crate::processor::limits::check_limits;
 use crate::processor::require;
-use crate::state::helpers::{
-    get_betting_info, get_game_info, get_supported_token_info, get_type_price_info, get_user_info,
-};
-use borsh::BorshSerialize;
+use crate::processor::sessions::use_session;
+use crate::processor::token_stats::create_token_stats;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use crate::state::structs::{SupportedToken, TokenStats, TypePrice};
 use num_traits::ToPrimitive;
 use solana_program::account_info::{next_account_info, AccountInfo};
 use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
-use solana_program::program::invoke;
+use solana_program::program::{invoke, invoke_signed};
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
 use solana_program::sysvar::Sysvar;
 
+/// Joins the game of `user_master` for the payer or, when `session_user` is set, for that user
+/// through the session the payer holds.
 pub fn bet_with_join(
     accounts: &[AccountInfo],
     program_id: &Pubkey,
     user_master: Pubkey,
     t: u64,
     support_bot: bool,
+    session_user: Option<Pubkey>,
 ) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
@@ -31,91 +42,111 @@ pub fn bet_with_join(
         return Err(ContractError::UnauthorisedAccess.into());
     }
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
-
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    let (type_price, _) =
-        Pubkey::find_program_address(&[TYPE_PRICE, t.to_string().as_bytes()], program_id);
-
-    if *accounts.type_price.key != type_price {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let type_price_info = ProgramAccount::<TypePrice>::load(
+        accounts.type_price,
+        program_id,
+        &[TYPE_PRICE, t.to_string().as_bytes()],
+    )?;
 
-    let type_price_info = get_type_price_info(&accounts.type_price.data.borrow())?;
+    require(!type_price_info.paused, ContractError::TypePaused)?;
 
     let value = type_price_info.price;
 
-    let (token_pda, _) =
-        Pubkey::find_program_address(&[WHITELIST, &accounts.token.key.to_bytes()], program_id);
-
-    if *accounts.supported_token.key != token_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let (user_pda, _) =
-        Pubkey::find_program_address(&[USER, &accounts.payer.key.to_bytes()], program_id);
-
-    if *accounts.user.key != user_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let (user_master_pda, _) =
-        Pubkey::find_program_address(&[USER, &user_master.to_bytes()], program_id);
+    let player = match session_user {
+        Some(user) => {
+            use_session(
+                accounts.session,
+                program_id,
+                accounts.payer.key,
+                &user,
+                value,
+            )?;
+            user
+        }
+        None => *accounts.payer.key,
+    };
 
-    if *accounts.user_master.key != user_master_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    require(player != user_master, ContractError::CannotJoinOwnGame)?;
 
-    let (game_pda, _) = Pubkey::find_program_address(&[GAME, &user_master.to_bytes()], program_id);
+    let supported_token_info = ProgramAccount::<SupportedToken>::load(
+        accounts.supported_token,
+        program_id,
+        &[WHITELIST, &accounts.token.key.to_bytes()],
+    )?;
 
-    if *accounts.game.key != game_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let mut user_info =
+        PodAccount::<PodUser>::load(accounts.user, program_id, &[USER, &player.to_bytes()])?;
+    let user_master_info = PodAccount::<PodUser>::load(
+        accounts.user_master,
+        program_id,
+        &[USER, &user_master.to_bytes()],
+    )?;
 
-    let mut user_info = get_user_info(&accounts.user.data.borrow())?;
-    let user_master_info = get_user_info(&accounts.user_master.data.borrow())?;
+    let game_info =
+        PodAccount::<PodGame>::load(accounts.game, program_id, &[GAME, &user_master.to_bytes()])?;
 
     require(
-        (&user_info.address == accounts.payer.key) || (user_info.is_bot == true),
-        "register first",
+        (user_info.address == player) || (user_info.is_bot.get() == true),
+        ContractError::NotRegistered,
     )?;
+    check_not_banned(&user_info)?;
+    check_not_banned(&user_master_info)?;
     require(
-        (user_info.in_game == false) || (user_info.is_bot == true),
-        "already in game",
+        (user_info.in_game.get() == false) || (user_info.is_bot.get() == true),
+        ContractError::AlreadyInGame,
     )?;
 
-    let betting_info = get_betting_info(&accounts.pda.data.borrow())?;
+    require(!betting_info.shutdown.get(), ContractError::Shutdown)?;
 
-    require(betting_info.accept_bets, "bets locked")?;
+    require(
+        betting_info.pause_flags & PAUSE_JOIN_GAME == 0,
+        ContractError::JoinGamesPaused,
+    )?;
 
-    let supported_token_info = get_supported_token_info(&accounts.supported_token.data.borrow())?;
+    // the session transfer passes the betting PDA on
+    let betting_bump = betting_info.bump;
+    drop(betting_info);
 
     require(
         supported_token_info.mint == *accounts.token.key,
-        "Token is not supported",
+        ContractError::UnsupportedToken,
     )?;
+    require(!supported_token_info.paused, ContractError::TokenPaused)?;
 
     require(
         supported_token_info.feed == *accounts.feed_account.key,
-        "Wrong feed for this token",
+        ContractError::InvalidFeed,
     )?;
 
-    user_info.support_bots = support_bot;
-    user_info.in_game = true;
-    user_info.serialize(&mut &mut accounts.user.data.borrow_mut()[..])?;
+    if user_info.is_bot.get() {
+        check_bot_limits(accounts.bot_info, program_id, &user_info, value)?;
+    }
+
+    check_limits(&mut user_info, value)?;
 
-    let game_info = get_game_info(&accounts.game.data.borrow())?;
+    user_info.support_bots = support_bot.into();
+    user_info.in_game = true.into();
+    user_info.active_games += 1;
 
-    if !game_info.closed {
-        require(game_info.type_price == value, "Wrong type price")?;
+    if !game_info.closed.get() {
+        require(
+            game_info.gamer2 == Pubkey::default(),
+            ContractError::GameAlreadyStarted,
+        )?;
+        require(
+            game_info.type_price == value,
+            ContractError::TypePriceMismatch,
+        )?;
         if !support_bot {
-            require(!user_master_info.is_bot, "User doesn't support bots")?;
+            require(
+                !user_master_info.is_bot.get(),
+                ContractError::BotsNotSupported,
+            )?;
         }
-        if !user_master_info.support_bots {
-            require(!user_info.is_bot, "User doesn't support bots")?;
+        if !user_master_info.support_bots.get() {
+            require(!user_info.is_bot.get(), ContractError::BotsNotSupported)?;
         }
 
         let convert_value: i128 = chainlink_solana::latest_round_data(
@@ -126,22 +157,25 @@ pub fn bet_with_join(
 
         let answer = convert_value.to_u64().unwrap();
 
-        if &spl_associated_token_account::get_associated_token_address(
-            accounts.payer.key,
-            accounts.token.key,
-        ) != accounts.source.key
+        let amount = value * PRECISION * PRECISION / answer;
+
+        if spl_associated_token_account::get_associated_token_address(&player, accounts.token.key)
+            != *accounts.source.key
         {
             return Err(ContractError::InvalidInstructionData.into());
         }
 
         if &spl_associated_token_account::get_associated_token_address(
-            &game_pda,
+            accounts.game.key,
             accounts.token.key,
         ) != accounts.destination.key
         {
             return Err(ContractError::InvalidInstructionData.into());
         }
 
+        // the game is seated before its token account is created, which passes the game on
+        join_game(&accounts, game_info, &player, amount)?;
+
         if accounts.destination.owner != accounts.token_program.key {
             invoke(
                 &spl_associated_token_account::create_associated_token_account(
@@ -162,29 +196,63 @@ pub fn bet_with_join(
             )?;
         }
 
-        invoke(
-            &spl_token::instruction::transfer(
-                accounts.token_program.key,
-                accounts.source.key,
-                accounts.destination.key,
-                accounts.payer.key,
-                &[],
-                value * PRECISION * PRECISION / answer,
-            )?,
-            &[
-                accounts.source.clone(),
-                accounts.destination.clone(),
-                accounts.payer.clone(),
-                accounts.token_program.clone(),
-            ],
+        if session_user.is_some() {
+            // the player approved the betting PDA as the delegate of their token account
+            invoke_signed(
+                &spl_token::instruction::transfer(
+                    accounts.token_program.key,
+                    accounts.source.key,
+                    accounts.destination.key,
+                    accounts.pda.key,
+                    &[],
+                    amount,
+                )?,
+                &[
+                    accounts.source.clone(),
+                    accounts.destination.clone(),
+                    accounts.pda.clone(),
+                    accounts.token_program.clone(),
+                ],
+                &[&[BETTING, &[betting_bump]]],
+            )?;
+        } else {
+            invoke(
+                &spl_token::instruction::transfer(
+                    accounts.token_program.key,
+                    accounts.source.key,
+                    accounts.destination.key,
+                    accounts.payer.key,
+                    &[],
+                    amount,
+                )?,
+                &[
+                    accounts.source.clone(),
+                    accounts.destination.clone(),
+                    accounts.payer.clone(),
+                    accounts.token_program.clone(),
+                ],
+            )?;
+        }
+
+        let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+        create_token_stats(
+            accounts.payer,
+            accounts.system_program,
+            accounts.stats,
+            rent,
+            program_id,
+            accounts.token.key,
         )?;
 
-        join_game(
-            accounts,
+        let mut stats_info = ProgramAccount::<TokenStats>::load(
+            accounts.stats,
             program_id,
-            user_master,
-            value * PRECISION * PRECISION / answer,
+            &[STATS, &accounts.token.key.to_bytes()],
         )?;
+
+        stats_info.staked_volume += amount;
+        stats_info.save()?;
     } else {
         return Err(ContractError::InvalidInstructionData.into());
     }
@@ -192,29 +260,27 @@ pub fn bet_with_join(
     Ok(())
 }
 
+/// Seats `player` in `game_info`, the game `bet_with_join` loaded. The view is dropped on return.
 pub fn join_game(
-    accounts: Accounts,
-    program_id: &Pubkey,
-    user_master: Pubkey,
+    accounts: &Accounts,
+    mut game_info: PodAccount<PodGame>,
+    player: &Pubkey,
     amount: u64,
 ) -> ProgramResult {
     let clock = Clock::get()?;
 
-    let (game_pda, _) = Pubkey::find_program_address(&[GAME, &user_master.to_bytes()], program_id);
-
-    if accounts.game.key != &game_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let mut game_info = get_game_info(&accounts.game.data.borrow())?;
-
-    require(*accounts.payer.key != user_master, "double registration")?;
-
-    game_info.gamer2 = *accounts.payer.key;
+    game_info.gamer2 = *player;
     game_info.token2 = *accounts.token.key;
     game_info.amount2 = amount;
     game_info.latest_bet = clock.unix_timestamp as u64;
-    game_info.serialize(&mut &mut accounts.game.data.borrow_mut()[..])?;
+
+    BettingEvent::GameJoined {
+        game: *accounts.game.key,
+        gamer: *player,
+        token: *accounts.token.key,
+        amount,
+    }
+    .emit()?;
 
     Ok(())
 }
@@ -237,6 +303,10 @@ pub struct Accounts<'a, 'b> {
     pub token: &'a AccountInfo<'b>,
     pub token_assoc: &'a AccountInfo<'b>,
     pub type_price: &'a AccountInfo<'b>,
+    pub stats: &'a AccountInfo<'b>,
+    pub bot_info: Option<&'a AccountInfo<'b>>,
+    /// Only read by `SessionJoinGame`.
+    pub session: Option<&'a AccountInfo<'b>>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -261,6 +331,9 @@ impl<'a, 'b> Accounts<'a, 'b> {
             token: next_account_info(acc_iter)?,
             token_assoc: next_account_info(acc_iter)?,
             type_price: next_account_info(acc_iter)?,
+            stats: next_account_info(acc_iter)?,
+            bot_info: acc_iter.next(),
+            session: acc_iter.next(),
         })
     }
 }
@@ -0,0 +1,175 @@
+use crate::consts::{LIMITS_COOLING_OFF, SECONDS_IN_THE_DAY, USER};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::require;
+use crate::state::accounts::PodAccount;
+use crate::state::pod::PodUser;
+use crate::state::structs::Limits;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::clock::Clock;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
+
+/// Tighter limits apply at once. When any limit is loosened, the requested limits are queued
+/// and replace the current ones after `LIMITS_COOLING_OFF`; a later `SetLimits` replaces the
+/// queued ones. A running exclusion can only be extended, never shortened or lifted.
+pub fn set_limits(accounts: &[AccountInfo], program_id: &Pubkey, limits: Limits) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    let mut user_info = PodAccount::<PodUser>::load(
+        accounts.user,
+        program_id,
+        &[USER, &accounts.payer.key.to_bytes()],
+    )?;
+
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    apply_pending_limits(&mut user_info, now);
+
+    let current = user_info.limits;
+    // an exclusion that has run out is no longer a limit to loosen
+    let excluded_until = if current.excluded_until > now {
+        current.excluded_until.max(limits.excluded_until)
+    } else {
+        limits.excluded_until
+    };
+    let limits = Limits {
+        excluded_until,
+        ..limits
+    };
+    let tightened = Limits {
+        max_type_price: tighter_cap(current.max_type_price, limits.max_type_price),
+        max_daily_games: tighter_cap(current.max_daily_games, limits.max_daily_games),
+        daily_loss_limit: tighter_cap(current.daily_loss_limit, limits.daily_loss_limit),
+        excluded_until,
+    };
+
+    user_info.limits = tightened;
+
+    if tightened == limits {
+        user_info.pending_limits = Limits::default();
+        user_info.pending_limits_at = 0;
+    } else {
+        user_info.pending_limits = limits;
+        user_info.pending_limits_at = now + LIMITS_COOLING_OFF;
+    }
+
+    BettingEvent::LimitsChanged {
+        user: *accounts.payer.key,
+        limits: user_info.limits,
+        pending_limits: user_info.pending_limits,
+        pending_limits_at: user_info.pending_limits_at,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Enforces the user's limits on a new game of `type_price` and counts the game for the day.
+pub fn check_limits(user_info: &mut PodUser, type_price: u64) -> ProgramResult {
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    apply_pending_limits(user_info, now);
+    roll_daily_counters(user_info, now);
+
+    let limits = user_info.limits;
+
+    require(now >= limits.excluded_until, ContractError::SelfExcluded)?;
+    require(
+        limits.max_type_price == 0 || type_price <= limits.max_type_price,
+        ContractError::StakeLimit,
+    )?;
+    require(
+        limits.max_daily_games == 0 || user_info.daily_games < limits.max_daily_games,
+        ContractError::DailyGamesLimit,
+    )?;
+    // the stake of this game counts as lost until it is settled
+    require(
+        limits.daily_loss_limit == 0
+            || user_info.daily_loss + type_price <= limits.daily_loss_limit,
+        ContractError::DailyLossLimit,
+    )?;
+
+    user_info.daily_games += 1;
+
+    Ok(())
+}
+
+/// Fails while the user has any limit in effect or a `SetLimits` waiting out its cooling-off,
+/// so that closing the account can't be used to drop them.
+pub fn check_no_limits(user_info: &PodUser) -> ProgramResult {
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    let limits = Limits {
+        excluded_until: 0,
+        ..user_info.limits
+    };
+
+    require(
+        limits == Limits::default()
+            && now >= user_info.limits.excluded_until
+            && user_info.pending_limits_at == 0,
+        ContractError::LimitsActive,
+    )
+}
+
+/// Counts a lost stake towards the user's daily loss.
+pub fn record_loss(user_info: &mut PodUser, type_price: u64) -> ProgramResult {
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    roll_daily_counters(user_info, now);
+    user_info.daily_loss += type_price;
+
+    Ok(())
+}
+
+fn apply_pending_limits(user_info: &mut PodUser, now: u64) {
+    if user_info.pending_limits_at != 0 && now >= user_info.pending_limits_at {
+        user_info.limits = user_info.pending_limits;
+        user_info.pending_limits = Limits::default();
+        user_info.pending_limits_at = 0;
+    }
+}
+
+/// Opens a new 24-hour window at `now` once the current one has run out. The window starts
+/// with the first game or loss after the previous one ended.
+fn roll_daily_counters(user_info: &mut PodUser, now: u64) {
+    if now >= user_info.limits_day.saturating_add(SECONDS_IN_THE_DAY) {
+        user_info.limits_day = now;
+        user_info.daily_games = 0;
+        user_info.daily_loss = 0;
+    }
+}
+
+/// The stricter of two caps where 0 means no cap.
+fn tighter_cap(current: u64, requested: u64) -> u64 {
+    match (current, requested) {
+        (0, requested) => requested,
+        (current, 0) => current,
+        (current, requested) => current.min(requested),
+    }
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub user: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            user: next_account_info(acc_iter)?,
+        })
+    }
+}
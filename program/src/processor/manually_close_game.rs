@@ -1,8 +1,11 @@
-use crate::consts::{ADMIN, BETTING, GAME, USER, WHITELIST};
+use crate::consts::{BETTING, GAME, STATS, USER, WHITELIST};
 use crate::error::ContractError;
+use crate::events::BettingEvent;
 use crate::processor::require;
-use crate::state::helpers::{get_betting_info, get_game_info, get_user_info};
-use borsh::BorshSerialize;
+use crate::processor::user_stats::record_cancellation;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use crate::state::structs::{SupportedToken, TokenStats};
 use solana_program::account_info::{next_account_info, AccountInfo};
 use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
@@ -24,61 +27,51 @@ pub fn manually_close(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramR
         return Err(ContractError::UnauthorisedAccess.into());
     }
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let betting_info = get_betting_info(&accounts.pda.data.borrow())?;
-
-    let (game_pda, game_bump) =
-        Pubkey::find_program_address(&[GAME, &accounts.payer.key.to_bytes()], program_id);
-
-    if *accounts.game.key != game_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let mut game_info = get_game_info(&accounts.game.data.borrow())?;
+    let mut game_info = PodAccount::<PodGame>::load(
+        accounts.game,
+        program_id,
+        &[GAME, &accounts.payer.key.to_bytes()],
+    )?;
+    let game_bump = game_info.bump;
 
     require(
         (clock.unix_timestamp as u64) >= (game_info.latest_bet + betting_info.close_delay),
-        "Please wait",
+        ContractError::CloseDelayNotPassed,
     )?;
     require(
         &game_info.gamer1 == accounts.payer.key,
-        "Sender is not in the game",
+        ContractError::NotInGame,
     )?;
-    require(!game_info.closed, "Game already closed")?;
+    require(!game_info.closed.get(), ContractError::GameClosed)?;
     require(
         game_info.gamer2 == Pubkey::default(),
-        "Game started already",
+        ContractError::GameAlreadyStarted,
     )?;
 
-    game_info.closed = true;
-    game_info.serialize(&mut &mut accounts.game.data.borrow_mut()[..])?;
-
-    let (user_pda, _) =
-        Pubkey::find_program_address(&[USER, &accounts.payer.key.to_bytes()], program_id);
-
-    if *accounts.user.key != user_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let mut user_info = get_user_info(&accounts.user.data.borrow())?;
+    game_info.closed = true.into();
 
-    user_info.in_game = false;
-    user_info.serialize(&mut &mut accounts.user.data.borrow_mut()[..])?;
+    let mut user_info = PodAccount::<PodUser>::load(
+        accounts.user,
+        program_id,
+        &[USER, &accounts.payer.key.to_bytes()],
+    )?;
 
-    let (token_pda, _) =
-        Pubkey::find_program_address(&[WHITELIST, &accounts.token.key.to_bytes()], program_id);
+    user_info.in_game = false.into();
+    user_info.active_games = user_info.active_games.saturating_sub(1);
+    record_cancellation(&mut user_info, game_info.type_price * 5 / 100)?;
 
-    if *accounts.supported_token.key != token_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    ProgramAccount::<SupportedToken>::load(
+        accounts.supported_token,
+        program_id,
+        &[WHITELIST, &accounts.token.key.to_bytes()],
+    )?;
 
-    if &spl_associated_token_account::get_associated_token_address(&game_pda, accounts.token.key)
-        != accounts.source.key
+    if &spl_associated_token_account::get_associated_token_address(
+        accounts.game.key,
+        accounts.token.key,
+    ) != accounts.source.key
     {
         return Err(ContractError::InvalidInstructionData.into());
     }
@@ -91,7 +84,7 @@ pub fn manually_close(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramR
         return Err(ContractError::InvalidInstructionData.into());
     }
 
-    let admin = ADMIN.parse::<Pubkey>().unwrap();
+    let admin = betting_info.admin;
 
     if &spl_associated_token_account::get_associated_token_address(&admin, accounts.token.key)
         != accounts.owner_assoc.key
@@ -101,6 +94,25 @@ pub fn manually_close(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramR
 
     let fee = game_info.amount1 * 5 / 100;
 
+    let mut stats_info = ProgramAccount::<TokenStats>::load(
+        accounts.stats,
+        program_id,
+        &[STATS, &game_info.token1.to_bytes()],
+    )?;
+
+    stats_info.penalties += fee;
+    stats_info.save()?;
+
+    let (amount1, amount2, gamer1, gamer2) = (
+        game_info.amount1,
+        game_info.amount2,
+        game_info.gamer1,
+        game_info.gamer2,
+    );
+
+    // the transfers pass the game on as the authority of its token account
+    drop(game_info);
+
     if accounts.owner_assoc.owner != accounts.token_program.key {
         invoke(
             &spl_associated_token_account::create_associated_token_account(
@@ -166,7 +178,7 @@ pub fn manually_close(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramR
             accounts.destination.key,
             accounts.game.key,
             &[],
-            game_info.amount1 - fee,
+            amount1 - fee,
         )?,
         &[
             accounts.source.clone(),
@@ -194,6 +206,15 @@ pub fn manually_close(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramR
         &[&[GAME, &accounts.payer.key.to_bytes(), &[game_bump]]],
     )?;
 
+    BettingEvent::GameCancelled {
+        game: *accounts.game.key,
+        gamer1,
+        amount1,
+        gamer2,
+        amount2,
+    }
+    .emit()?;
+
     Ok(())
 }
 
@@ -213,6 +234,7 @@ pub struct Accounts<'a, 'b> {
     pub token_program: &'a AccountInfo<'b>,
     pub token: &'a AccountInfo<'b>,
     pub token_assoc: &'a AccountInfo<'b>,
+    pub stats: &'a AccountInfo<'b>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -235,6 +257,7 @@ impl<'a, 'b> Accounts<'a, 'b> {
             token_program: next_account_info(acc_iter)?,
             token: next_account_info(acc_iter)?,
             token_assoc: next_account_info(acc_iter)?,
+            stats: next_account_info(acc_iter)?,
         })
     }
 }
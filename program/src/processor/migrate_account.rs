@@ -0,0 +1,252 @@
+use crate::consts::DISCRIMINATOR_ACCOUNT_VERSION;
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::pda::{
+    find_betting_address, find_bot_info_address, find_bot_registry_address, find_config_address,
+    find_game_address, find_referral_code_address, find_referral_reward_address, find_role_address,
+    find_session_address, find_stats_address, find_supported_token_address,
+    find_type_price_address, find_user_address,
+};
+use crate::processor::require;
+use crate::state::helpers::{
+    get_account_version, get_betting_info, get_bot_info, get_bot_registry_info, get_game_info,
+    get_latest_version, get_pending_config_info, get_referral_code_info, get_referral_reward_info,
+    get_role_info, get_session_info, get_supported_token_info, get_token_stats_info,
+    get_type_price_info, get_user_info,
+};
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use crate::state::structs::{
+    AccountType, BotInfo, BotRegistry, PendingConfig, ReferralCode, ReferralReward, Role, Session,
+    SupportedToken, TokenStats, TypePrice,
+};
+use borsh::BorshSerialize;
+use bytemuck::bytes_of;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::invoke;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::sysvar::Sysvar;
+
+/// Rewrites an account stored in an older layout in the current one, resizing it with
+/// `realloc`. The payer covers the extra rent. `t` is only used for `TypePrice` accounts.
+pub fn migrate_account(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    account_type: AccountType,
+    t: u64,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    if accounts.account.owner != program_id {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    require(
+        get_account_version(&accounts.account.data.borrow(), account_type)?
+            != get_latest_version(account_type),
+        ContractError::AccountUpToDate,
+    )?;
+
+    // The expected address is derived from the decoded data, so an account of one type can't
+    // be migrated as another.
+    let discriminator = account_type.discriminator();
+    let version = DISCRIMINATOR_ACCOUNT_VERSION;
+
+    let (expected, data) = match account_type {
+        AccountType::BettingInfo => {
+            let mut info = get_betting_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_betting_address(program_id);
+            info.bump = bump;
+            (address, bytes_of(&PodBettingInfo::from(&info)).to_vec())
+        }
+        AccountType::Game => {
+            let mut info = get_game_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_game_address(program_id, &info.gamer1);
+            info.bump = bump;
+            (address, bytes_of(&PodGame::from(&info)).to_vec())
+        }
+        AccountType::User => {
+            let mut info = get_user_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_user_address(program_id, &info.address);
+            info.bump = bump;
+            (address, bytes_of(&PodUser::from(&info)).to_vec())
+        }
+        AccountType::SupportedToken => {
+            let info = get_supported_token_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_supported_token_address(program_id, &info.mint);
+            let info = SupportedToken {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::TypePrice => {
+            let info = get_type_price_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_type_price_address(program_id, t);
+            let info = TypePrice {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::TokenStats => {
+            let info = get_token_stats_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_stats_address(program_id, &info.mint);
+            let info = TokenStats {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::ReferralCode => {
+            let info = get_referral_code_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_referral_code_address(program_id, &info.code);
+            let info = ReferralCode {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::ReferralReward => {
+            let info = get_referral_reward_info(&accounts.account.data.borrow())?;
+            let (address, bump) =
+                find_referral_reward_address(program_id, &info.referrer, &info.mint);
+            let info = ReferralReward {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::Role => {
+            let info = get_role_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_role_address(program_id, &info.address);
+            let info = Role {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::PendingConfig => {
+            let info = get_pending_config_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_config_address(program_id);
+            let info = PendingConfig {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::BotInfo => {
+            let info = get_bot_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_bot_info_address(program_id, &info.bot);
+            let info = BotInfo {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::BotRegistry => {
+            let info = get_bot_registry_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_bot_registry_address(program_id);
+            let info = BotRegistry {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::Session => {
+            let info = get_session_info(&accounts.account.data.borrow())?;
+            let (address, bump) = find_session_address(program_id, &info.user);
+            let info = Session {
+                discriminator,
+                version,
+                bump,
+                ..info
+            };
+            (address, info.try_to_vec()?)
+        }
+        AccountType::Uninitialized => return Err(ContractError::InvalidInstructionData.into()),
+    };
+
+    if *accounts.account.key != expected {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+    let required_lamports = rent
+        .minimum_balance(data.len())
+        .saturating_sub(accounts.account.lamports());
+
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(
+                accounts.payer.key,
+                accounts.account.key,
+                required_lamports,
+            ),
+            &[
+                accounts.payer.clone(),
+                accounts.account.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+    }
+
+    accounts.account.realloc(data.len(), false)?;
+    accounts.account.data.borrow_mut().copy_from_slice(&data);
+
+    BettingEvent::AccountMigrated {
+        account: *accounts.account.key,
+        account_type,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub account: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            account: next_account_info(acc_iter)?,
+        })
+    }
+}
@@ -1,31 +1,61 @@
 pub mod add_bot;
 pub mod add_supported_token;
+pub mod ban;
 pub mod bet;
-pub mod close_game;
+pub mod bots;
+pub mod claim_referral_rewards;
+pub mod config_change;
+pub mod emergency;
 pub mod forced_close_game;
 pub mod init;
 pub mod join_game;
+pub mod limits;
 pub mod manually_close_game;
+pub mod migrate_account;
+pub mod pause;
+pub mod profile;
+pub mod referral_codes;
+pub mod referral_rewards;
 pub mod registration;
+pub mod roles;
+pub mod sessions;
 pub mod set_type_price;
 pub mod setters;
+pub mod settle_game;
+pub mod token_stats;
+pub mod user_stats;
 
 use crate::error::ContractError;
 use crate::instruction::BettingInstruction;
 use crate::processor::add_bot::add_bot;
 use crate::processor::add_supported_token::add_supported_token;
+use crate::processor::ban::{ban_user, unban_user};
 use crate::processor::bet::bet;
-use crate::processor::close_game::close;
+use crate::processor::bots::{remove_bot, suspend_bot};
+use crate::processor::claim_referral_rewards::claim_referral_rewards;
+use crate::processor::config_change::{
+    cancel_config_change, execute_config_change, queue_config_change,
+};
+use crate::processor::emergency::{emergency_refund, emergency_shutdown};
 use crate::processor::forced_close_game::forced_close;
 use crate::processor::init::init;
 use crate::processor::join_game::bet_with_join;
+use crate::processor::limits::set_limits;
 use crate::processor::manually_close_game::manually_close;
+use crate::processor::migrate_account::migrate_account;
+use crate::processor::pause::{set_token_paused, set_type_paused};
+use crate::processor::profile::{change_referrer, deregister, set_credential_hash, update_profile};
+use crate::processor::referral_codes::{
+    claim_referral_code, release_referral_code, transfer_referral_code,
+};
 use crate::processor::registration::registration;
+use crate::processor::roles::{grant_role, revoke_role};
+use crate::processor::sessions::{create_session, revoke_session};
 use crate::processor::set_type_price::set_type_price;
 use crate::processor::setters::{
-    change_close_delay, lock_bets, new_manager, set_admin_fee, set_global_fee, set_transaction_fee,
-    set_winner_fee, unlock_bets,
+    accept_admin, lock_bets, propose_admin, set_pause_flags, unlock_bets,
 };
+use crate::processor::settle_game::{distribute, settle};
 use borsh::BorshDeserialize;
 use solana_program::account_info::AccountInfo;
 use solana_program::entrypoint::ProgramResult;
@@ -54,21 +84,18 @@ impl Processor {
 
         match instruction {
             BettingInstruction::Init {
-                manager,
+                admin,
                 supported_token,
                 feed,
                 is_stablecoin,
             } => init(
                 accounts,
                 program_id,
-                manager,
+                admin,
                 supported_token,
                 feed,
                 is_stablecoin,
             )?,
-            BettingInstruction::ChangeCloseDelay { new_delay } => {
-                change_close_delay(accounts, program_id, new_delay)?
-            }
             BettingInstruction::LockBets => lock_bets(accounts, program_id)?,
             BettingInstruction::UnlockBets => unlock_bets(accounts, program_id)?,
             BettingInstruction::AddSupportedToken {
@@ -76,37 +103,146 @@ impl Processor {
                 feed,
                 is_stablecoin,
             } => add_supported_token(accounts, program_id, supported_token, feed, is_stablecoin)?,
-            BettingInstruction::Registration { referrer, password } => {
-                registration(accounts, program_id, referrer, password)?
-            }
-            BettingInstruction::NewManager { manager } => {
-                new_manager(accounts, program_id, manager)?
-            }
-            BettingInstruction::SetGlobalFee { fee } => set_global_fee(accounts, program_id, fee)?,
-            BettingInstruction::SetAdminFee { fee } => set_admin_fee(accounts, program_id, fee)?,
-            BettingInstruction::SetWinnerFee { fee } => set_winner_fee(accounts, program_id, fee)?,
-            BettingInstruction::SetTransactionFee { fee } => {
-                set_transaction_fee(accounts, program_id, fee)?
-            }
-            BettingInstruction::AddBot { bot } => add_bot(accounts, program_id, bot)?,
+            BettingInstruction::Registration {
+                referrer,
+                credential_hash,
+            } => registration(accounts, program_id, referrer, credential_hash)?,
+            BettingInstruction::AddBot {
+                bot,
+                max_games,
+                max_type_price,
+                daily_volume_limit,
+            } => add_bot(
+                accounts,
+                program_id,
+                bot,
+                max_games,
+                max_type_price,
+                daily_volume_limit,
+            )?,
             BettingInstruction::NewGame { t, support_bot } => {
-                bet(accounts, program_id, t, support_bot)?
+                bet(accounts, program_id, t, support_bot, None)?
             }
             BettingInstruction::JoinGame {
                 t,
                 support_bot,
                 user_master,
-            } => bet_with_join(accounts, program_id, user_master, t, support_bot)?,
+            } => bet_with_join(accounts, program_id, user_master, t, support_bot, None)?,
             BettingInstruction::ForcedClose { user } => forced_close(accounts, program_id, user)?,
             BettingInstruction::ManuallyClose => manually_close(accounts, program_id)?,
-            BettingInstruction::Close {
+            BettingInstruction::Settle {
                 user,
                 winner_address,
                 t,
-            } => close(accounts, program_id, user, winner_address, t)?,
+            } => settle(accounts, program_id, user, winner_address, t)?,
             BettingInstruction::SetTypePrice { t, price } => {
                 set_type_price(accounts, program_id, t, price)?
             }
+            BettingInstruction::ClaimReferralRewards => {
+                claim_referral_rewards(accounts, program_id)?
+            }
+            BettingInstruction::ClaimReferralCode { code } => {
+                claim_referral_code(accounts, program_id, code)?
+            }
+            BettingInstruction::TransferReferralCode { code, new_owner } => {
+                transfer_referral_code(accounts, program_id, code, new_owner)?
+            }
+            BettingInstruction::ReleaseReferralCode { code } => {
+                release_referral_code(accounts, program_id, code)?
+            }
+            BettingInstruction::ProposeAdmin { admin } => {
+                propose_admin(accounts, program_id, admin)?
+            }
+            BettingInstruction::AcceptAdmin => accept_admin(accounts, program_id)?,
+            BettingInstruction::GrantRole {
+                address,
+                permissions,
+            } => grant_role(accounts, program_id, address, permissions)?,
+            BettingInstruction::RevokeRole {
+                address,
+                permissions,
+            } => revoke_role(accounts, program_id, address, permissions)?,
+            BettingInstruction::QueueConfigChange { config } => {
+                queue_config_change(accounts, program_id, config)?
+            }
+            BettingInstruction::ExecuteConfigChange => execute_config_change(accounts, program_id)?,
+            BettingInstruction::CancelConfigChange => cancel_config_change(accounts, program_id)?,
+            BettingInstruction::SetPauseFlags { flags } => {
+                set_pause_flags(accounts, program_id, flags)?
+            }
+            BettingInstruction::SetTokenPaused {
+                supported_token,
+                paused,
+            } => set_token_paused(accounts, program_id, supported_token, paused)?,
+            BettingInstruction::SetTypePaused { t, paused } => {
+                set_type_paused(accounts, program_id, t, paused)?
+            }
+            BettingInstruction::RemoveBot { bot } => remove_bot(accounts, program_id, bot)?,
+            BettingInstruction::SuspendBot { bot, suspended } => {
+                suspend_bot(accounts, program_id, bot, suspended)?
+            }
+            BettingInstruction::BanUser {
+                user,
+                reason,
+                expiry,
+            } => ban_user(accounts, program_id, user, reason, expiry)?,
+            BettingInstruction::UnbanUser { user } => unban_user(accounts, program_id, user)?,
+            BettingInstruction::MigrateAccount { account_type, t } => {
+                migrate_account(accounts, program_id, account_type, t)?
+            }
+            BettingInstruction::EmergencyShutdown { shutdown } => {
+                emergency_shutdown(accounts, program_id, shutdown)?
+            }
+            BettingInstruction::EmergencyRefund { game } => {
+                emergency_refund(accounts, program_id, game)?
+            }
+            BettingInstruction::Distribute { user, index } => {
+                distribute(accounts, program_id, user, index)?
+            }
+            BettingInstruction::SetCredentialHash { credential_hash } => {
+                set_credential_hash(accounts, program_id, credential_hash)?
+            }
+            BettingInstruction::UpdateProfile {
+                nickname,
+                avatar_uri,
+            } => update_profile(accounts, program_id, nickname, avatar_uri)?,
+            BettingInstruction::ChangeReferrer { referrer } => {
+                change_referrer(accounts, program_id, referrer)?
+            }
+            BettingInstruction::Deregister => deregister(accounts, program_id)?,
+            BettingInstruction::SetLimits { limits } => set_limits(accounts, program_id, limits)?,
+            BettingInstruction::CreateSession {
+                delegate,
+                expires_at,
+                max_stake_per_game,
+                max_total,
+            } => create_session(
+                accounts,
+                program_id,
+                delegate,
+                expires_at,
+                max_stake_per_game,
+                max_total,
+            )?,
+            BettingInstruction::RevokeSession => revoke_session(accounts, program_id)?,
+            BettingInstruction::SessionNewGame {
+                t,
+                support_bot,
+                user,
+            } => bet(accounts, program_id, t, support_bot, Some(user))?,
+            BettingInstruction::SessionJoinGame {
+                t,
+                support_bot,
+                user_master,
+                user,
+            } => bet_with_join(
+                accounts,
+                program_id,
+                user_master,
+                t,
+                support_bot,
+                Some(user),
+            )?,
         };
 
         Ok(())
@@ -146,13 +282,3 @@ impl PrintableErr for ContractError {
         ProgramError::from(self)
     }
 }
-
-impl PrintableErr for &str {
-    fn print(&self) {
-        msg!("Error: {}", self);
-    }
-
-    fn err(self) -> ProgramError {
-        ProgramError::Custom(255)
-    }
-}
@@ -0,0 +1,100 @@
+use crate::consts::{BETTING, PAUSER, TYPE_PRICE, WHITELIST};
+use crate::events::BettingEvent;
+use crate::processor::roles::check_permission;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::PodBettingInfo;
+use crate::state::structs::{SupportedToken, TypePrice};
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+/// Freezes or unfreezes games in a single token without touching the rest of the platform.
+pub fn set_token_paused(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    supported_token: Pubkey,
+    paused: bool,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    check_pauser(&accounts, program_id)?;
+
+    let mut supported_token_info = ProgramAccount::<SupportedToken>::load(
+        accounts.target,
+        program_id,
+        &[WHITELIST, &supported_token.to_bytes()],
+    )?;
+
+    supported_token_info.paused = paused;
+    supported_token_info.save()?;
+
+    BettingEvent::TokenPaused {
+        mint: supported_token,
+        paused,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Freezes or unfreezes a single game type.
+pub fn set_type_paused(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    t: u64,
+    paused: bool,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    check_pauser(&accounts, program_id)?;
+
+    let mut type_price_info = ProgramAccount::<TypePrice>::load(
+        accounts.target,
+        program_id,
+        &[TYPE_PRICE, t.to_string().as_bytes()],
+    )?;
+
+    type_price_info.paused = paused;
+    type_price_info.save()?;
+
+    BettingEvent::TypePaused { t, paused }.emit()?;
+
+    Ok(())
+}
+
+fn check_pauser(accounts: &Accounts, program_id: &Pubkey) -> ProgramResult {
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        PAUSER,
+    )
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub target: &'a AccountInfo<'b>,
+    pub role: Option<&'a AccountInfo<'b>>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            target: next_account_info(acc_iter)?,
+            role: acc_iter.next(),
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use crate::consts::{MAX_AVATAR_URI_LEN, MAX_NICKNAME_LEN, USER};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::instruction::Referrer;
+use crate::processor::ban::check_not_banned;
+use crate::processor::bots::close_account;
+use crate::processor::limits::check_no_limits;
+use crate::processor::registration::resolve_referrer;
+use crate::processor::require;
+use crate::state::accounts::PodAccount;
+use crate::state::pod::{write_padded, PodUser};
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::hash::hashv;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+/// The commitment stored by `SetCredentialHash`. It is computed off-chain; the salt stays with
+/// the user, so the hash can't be matched against other accounts or a password list.
+pub fn credential_hash(wallet: &Pubkey, salt: &[u8], password: &str) -> [u8; 32] {
+    hashv(&[salt, &wallet.to_bytes(), password.as_bytes()]).to_bytes()
+}
+
+pub fn set_credential_hash(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    credential_hash: Option<[u8; 32]>,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let mut user_info = get_own_user(&accounts, program_id)?;
+
+    // a zeroed hash means none is set
+    user_info.credential_hash = credential_hash.unwrap_or_default();
+
+    BettingEvent::CredentialHashSet {
+        user: *accounts.payer.key,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+pub fn update_profile(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    nickname: String,
+    avatar_uri: String,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let mut user_info = get_own_user(&accounts, program_id)?;
+
+    check_not_banned(&user_info)?;
+    require(
+        nickname.len() <= MAX_NICKNAME_LEN,
+        ContractError::NicknameTooLong,
+    )?;
+    require(
+        avatar_uri.len() <= MAX_AVATAR_URI_LEN,
+        ContractError::AvatarUriTooLong,
+    )?;
+
+    write_padded(&nickname, &mut user_info.nickname);
+    write_padded(&avatar_uri, &mut user_info.avatar_uri);
+
+    BettingEvent::ProfileUpdated {
+        user: *accounts.payer.key,
+        nickname,
+        avatar_uri,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Allowed once, or any number of times while the user has no turnover, and never mid-game.
+pub fn change_referrer(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    referrer: Referrer,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let mut user_info = get_own_user(&accounts, program_id)?;
+
+    check_not_banned(&user_info)?;
+    require(!user_info.in_game.get(), ContractError::AlreadyInGame)?;
+    require(
+        !user_info.referrer_changed.get() || user_info.turnover == 0,
+        ContractError::ReferrerLocked,
+    )?;
+
+    let referrer = resolve_referrer(
+        referrer,
+        accounts.referral_code,
+        program_id,
+        accounts.payer.key,
+    )?;
+
+    user_info.referrer = referrer;
+    user_info.referrer_changed = true.into();
+
+    BettingEvent::ReferrerChanged {
+        user: *accounts.payer.key,
+        referrer,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Closes the user's account and refunds its rent to the user. Not while a ban or a limit is in
+/// effect, which registering again would otherwise clear.
+pub fn deregister(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let user_info = get_own_user(&accounts, program_id)?;
+
+    check_not_banned(&user_info)?;
+    check_no_limits(&user_info)?;
+    require(!user_info.is_bot.get(), ContractError::CannotDeregisterBot)?;
+    require(
+        !user_info.in_game.get() && user_info.active_games == 0,
+        ContractError::AlreadyInGame,
+    )?;
+    drop(user_info);
+
+    close_account(accounts.user, accounts.payer)?;
+
+    BettingEvent::UserDeregistered {
+        user: *accounts.payer.key,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+fn get_own_user<'a, 'b>(
+    accounts: &Accounts<'a, 'b>,
+    program_id: &Pubkey,
+) -> Result<PodAccount<'a, 'b, PodUser>, ProgramError> {
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    PodAccount::<PodUser>::load(
+        accounts.user,
+        program_id,
+        &[USER, &accounts.payer.key.to_bytes()],
+    )
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub user: &'a AccountInfo<'b>,
+    pub referral_code: Option<&'a AccountInfo<'b>>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            user: next_account_info(acc_iter)?,
+            referral_code: acc_iter.next(),
+        })
+    }
+}
@@ -0,0 +1,215 @@
+use crate::consts::{
+    DISCRIMINATOR_ACCOUNT_VERSION, MAX_REFERRAL_CODE_LEN, MIN_REFERRAL_CODE_LEN, REFERRAL_CODE,
+    RESERVED_SPACE,
+};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::state::accounts::ProgramAccount;
+use crate::state::structs::{AccountType, ReferralCode};
+use borsh::BorshSerialize;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::sysvar::Sysvar;
+use std::convert::TryInto;
+
+/// Codes are lowercase so that two partners can't hold codes differing only by case.
+pub fn validate_referral_code(code: &str) -> ProgramResult {
+    if code.len() < MIN_REFERRAL_CODE_LEN
+        || code.len() > MAX_REFERRAL_CODE_LEN
+        || !code
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'_' || c == b'-')
+    {
+        return Err(ContractError::InvalidReferralCode.into());
+    }
+
+    Ok(())
+}
+
+/// Resolves a claimed code to the wallet of its owner.
+pub fn get_referral_code_owner(
+    referral_code: &AccountInfo,
+    program_id: &Pubkey,
+    code: &str,
+) -> Result<Pubkey, ProgramError> {
+    validate_referral_code(code)?;
+
+    let referral_code = ProgramAccount::<ReferralCode>::load(
+        referral_code,
+        program_id,
+        &[REFERRAL_CODE, code.as_bytes()],
+    )
+    .map_err(|_| ContractError::InvalidReferralCode)?;
+
+    Ok(referral_code.owner)
+}
+
+pub fn claim_referral_code(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    code: String,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    validate_referral_code(&code)?;
+
+    let (code_pda, code_bump) =
+        Pubkey::find_program_address(&[REFERRAL_CODE, code.as_bytes()], program_id);
+
+    if *accounts.referral_code.key != code_pda {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    if accounts.referral_code.owner == program_id {
+        return Err(ContractError::ReferralCodeTaken.into());
+    }
+
+    let referral_code = ReferralCode {
+        discriminator: AccountType::ReferralCode.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        code,
+        owner: *accounts.payer.key,
+        bump: code_bump,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+
+    let size = (referral_code.try_to_vec()?).len();
+
+    let required_lamports = rent
+        .minimum_balance(size)
+        .max(1)
+        .saturating_sub(accounts.referral_code.lamports());
+
+    invoke(
+        &system_instruction::transfer(accounts.payer.key, &code_pda, required_lamports),
+        &[
+            accounts.payer.clone(),
+            accounts.referral_code.clone(),
+            accounts.system_program.clone(),
+        ],
+    )?;
+
+    invoke_signed(
+        &system_instruction::allocate(&code_pda, size.try_into().unwrap()),
+        &[
+            accounts.referral_code.clone(),
+            accounts.system_program.clone(),
+        ],
+        &[&[REFERRAL_CODE, referral_code.code.as_bytes(), &[code_bump]]],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(&code_pda, program_id),
+        &[
+            accounts.referral_code.clone(),
+            accounts.system_program.clone(),
+        ],
+        &[&[REFERRAL_CODE, referral_code.code.as_bytes(), &[code_bump]]],
+    )?;
+
+    referral_code.serialize(&mut &mut accounts.referral_code.data.borrow_mut()[..])?;
+
+    BettingEvent::ReferralCodeClaimed {
+        code: referral_code.code.clone(),
+        owner: referral_code.owner,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+pub fn transfer_referral_code(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    code: String,
+    new_owner: Pubkey,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    validate_referral_code(&code)?;
+
+    let mut referral_code = ProgramAccount::<ReferralCode>::load(
+        accounts.referral_code,
+        program_id,
+        &[REFERRAL_CODE, code.as_bytes()],
+    )
+    .map_err(|_| ContractError::InvalidReferralCode)?;
+
+    if *accounts.payer.key != referral_code.owner || !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    referral_code.owner = new_owner;
+    referral_code.save()?;
+
+    BettingEvent::ReferralCodeTransferred {
+        code: referral_code.code.clone(),
+        owner: new_owner,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Closes the code PDA so anyone can claim the code again; the rent goes back to the owner.
+pub fn release_referral_code(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    code: String,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let owner = get_referral_code_owner(accounts.referral_code, program_id, &code)?;
+
+    if *accounts.payer.key != owner || !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    let lamports = accounts.referral_code.lamports();
+
+    **accounts.payer.lamports.borrow_mut() = accounts
+        .payer
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ContractError::OperationWithOverflow)?;
+    **accounts.referral_code.lamports.borrow_mut() = 0;
+
+    accounts.referral_code.data.borrow_mut().fill(0);
+
+    BettingEvent::ReferralCodeReleased { code }.emit()?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub referral_code: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            referral_code: next_account_info(acc_iter)?,
+        })
+    }
+}
@@ -0,0 +1,218 @@
+use crate::consts::{
+    DISCRIMINATOR_ACCOUNT_VERSION, MAX_REFERRAL_DEPTH, REFERRAL, RESERVED_SPACE, USER,
+};
+use crate::error::ContractError;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::helpers::get_user_info;
+use crate::state::pod::PodUser;
+use crate::state::structs::{AccountType, ReferralReward};
+use borsh::BorshSerialize;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+
+/// Opens the reward PDA of `referrer` in `mint` at the payer's expense, on the first reward or
+/// the first claim, whichever comes first. An existing account is left to the caller's
+/// `ProgramAccount::load`, which checks it against its stored bump.
+pub fn create_referral_reward<'a>(
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    reward: &AccountInfo<'a>,
+    rent: &Rent,
+    program_id: &Pubkey,
+    referrer: &Pubkey,
+    mint: &Pubkey,
+) -> ProgramResult {
+    if reward.owner == program_id {
+        return Ok(());
+    }
+
+    let (reward_pda, reward_bump) = Pubkey::find_program_address(
+        &[REFERRAL, &referrer.to_bytes(), &mint.to_bytes()],
+        program_id,
+    );
+
+    if *reward.key != reward_pda {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    let reward_info = ReferralReward {
+        discriminator: AccountType::ReferralReward.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        referrer: *referrer,
+        mint: *mint,
+        balance: 0,
+        claimed: 0,
+        bump: reward_bump,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+
+    let size = (reward_info.try_to_vec()?).len() as u64;
+
+    let required_lamports = rent
+        .minimum_balance(size as usize)
+        .max(1)
+        .saturating_sub(reward.lamports());
+
+    invoke(
+        &system_instruction::transfer(payer.key, &reward_pda, required_lamports),
+        &[payer.clone(), reward.clone(), system_program.clone()],
+    )?;
+
+    invoke_signed(
+        &system_instruction::allocate(&reward_pda, size),
+        &[reward.clone(), system_program.clone()],
+        &[&[
+            REFERRAL,
+            &referrer.to_bytes(),
+            &mint.to_bytes(),
+            &[reward_bump],
+        ]],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(&reward_pda, program_id),
+        &[reward.clone(), system_program.clone()],
+        &[&[
+            REFERRAL,
+            &referrer.to_bytes(),
+            &mint.to_bytes(),
+            &[reward_bump],
+        ]],
+    )?;
+
+    reward_info.serialize(&mut &mut reward.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Credits `amount` of `mint` to the open reward PDA of `referrer`. The tokens themselves are
+/// expected to be moved into the betting vault by the caller.
+pub fn accrue_referral_reward(
+    reward: &AccountInfo,
+    program_id: &Pubkey,
+    referrer: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let mut reward_info = ProgramAccount::<ReferralReward>::load(
+        reward,
+        program_id,
+        &[REFERRAL, &referrer.to_bytes(), &mint.to_bytes()],
+    )?;
+
+    reward_info.balance = reward_info
+        .balance
+        .checked_add(amount)
+        .ok_or(ContractError::OperationWithOverflow)?;
+    reward_info.save()
+}
+
+/// The referral chain of one player and the fee pool to split between its tiers.
+pub struct ReferralChain {
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub pool: u64,
+    pub tiers: [u64; MAX_REFERRAL_DEPTH],
+}
+
+/// Splits the pool of `referral` between its chain, tier by tier, and returns the amount accrued.
+/// The first referrer's reward PDA is passed explicitly; every further level takes the User PDA of
+/// the previous referrer followed by the reward PDA of the next one from `chain`.
+/// A referrer's reward PDA is opened at the payer's expense with their first reward.
+/// The walk stops at an empty or unregistered referrer, at a referrer already seen, or at
+/// `MAX_REFERRAL_DEPTH`, leaving the tiers it did not reach to the caller.
+pub fn accrue_referral_chain<'a, 'b, I>(
+    payer: &AccountInfo<'b>,
+    system_program: &AccountInfo<'b>,
+    rent: &Rent,
+    first_reward: &AccountInfo<'b>,
+    chain: &mut I,
+    program_id: &Pubkey,
+    referral: &ReferralChain,
+) -> Result<u64, ProgramError>
+where
+    I: Iterator<Item = &'a AccountInfo<'b>>,
+    'b: 'a,
+{
+    let mut referrer = referral.referrer;
+    let mut visited = vec![referral.player];
+    let mut paid = 0;
+
+    for (level, tier) in referral.tiers.iter().enumerate() {
+        if referrer == Pubkey::default() || visited.contains(&referrer) {
+            break;
+        }
+
+        let reward = if level == 0 {
+            first_reward
+        } else {
+            next_account_info(chain)?
+        };
+
+        let share = referral.pool * tier / 100;
+
+        if share != 0 {
+            create_referral_reward(
+                payer,
+                system_program,
+                reward,
+                rent,
+                program_id,
+                &referrer,
+                &referral.mint,
+            )?;
+            accrue_referral_reward(reward, program_id, &referrer, &referral.mint, share)?;
+            paid += share;
+        }
+
+        visited.push(referrer);
+
+        if level + 1 == MAX_REFERRAL_DEPTH {
+            break;
+        }
+
+        referrer = match get_referrer(next_account_info(chain)?, program_id, &referrer)? {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    Ok(paid)
+}
+
+/// The referrer of `player`, or `None` when `player` has no User PDA. Referral chains reach
+/// users who haven't played since the last layout change, so their accounts are decoded when
+/// they can't be viewed in place.
+pub fn get_referrer(
+    user: &AccountInfo,
+    program_id: &Pubkey,
+    player: &Pubkey,
+) -> Result<Option<Pubkey>, ProgramError> {
+    let seeds: &[&[u8]] = &[USER, &player.to_bytes()];
+
+    if user.owner != program_id {
+        if *user.key != Pubkey::find_program_address(seeds, program_id).0 {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
+
+        return Ok(None);
+    }
+
+    match PodAccount::<PodUser>::load(user, program_id, seeds) {
+        Ok(user_info) => Ok(Some(user_info.referrer)),
+        Err(err) if err == ContractError::MigrationRequired.into() => {
+            if *user.key != Pubkey::find_program_address(seeds, program_id).0 {
+                return Err(ContractError::InvalidInstructionData.into());
+            }
+
+            Ok(Some(get_user_info(&user.data.borrow())?.referrer))
+        }
+        Err(err) => Err(err),
+    }
+}
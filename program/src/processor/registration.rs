@@ -1,8 +1,14 @@
-use crate::consts::USER;
+use crate::consts::{DISCRIMINATOR_ACCOUNT_VERSION, USER};
 use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::instruction::Referrer;
+use crate::processor::referral_codes::get_referral_code_owner;
 use crate::processor::require;
-use crate::state::structs::User;
-use borsh::BorshSerialize;
+use crate::state::accounts::PodAccount;
+use crate::state::helpers::USER_LEN;
+use crate::state::pod::PodUser;
+use crate::state::structs::AccountType;
+use bytemuck::Zeroable;
 use solana_program::account_info::{next_account_info, AccountInfo};
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program::{invoke, invoke_signed};
@@ -16,8 +22,8 @@ use std::convert::TryInto;
 pub fn registration(
     accounts: &[AccountInfo],
     program_id: &Pubkey,
-    referrer: Pubkey,
-    password: String,
+    referrer: Referrer,
+    credential_hash: Option<[u8; 32]>,
 ) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
@@ -31,61 +37,94 @@ pub fn registration(
     }
 
     require(
-        &referrer != accounts.payer.key,
-        "refferer must not be equal to user wallet",
+        accounts.user.owner != program_id,
+        ContractError::AlreadyRegistered,
     )?;
 
-    let user = User {
+    let referrer = resolve_referrer(
+        referrer,
+        accounts.referral_code,
+        program_id,
+        accounts.payer.key,
+    )?;
+
+    let user = PodUser {
+        discriminator: AccountType::User.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        bump: data_address_bump,
         address: *accounts.payer.key,
         referrer,
-        in_game: false,
-        support_bots: false,
-        is_bot: false,
-        turnover: 0,
-        password,
+        credential_hash: credential_hash.unwrap_or_default(),
+        ..PodUser::zeroed()
     };
 
-    if accounts.user.owner != program_id {
-        let size = (user.try_to_vec()?).len();
-
-        let required_lamports = rent
-            .minimum_balance(size)
-            .max(1)
-            .saturating_sub(accounts.user.lamports());
-
-        invoke(
-            &system_instruction::transfer(accounts.payer.key, &data_address, required_lamports),
-            &[
-                accounts.payer.clone(),
-                accounts.user.clone(),
-                accounts.system_program.clone(),
-            ],
-        )?;
-
-        invoke_signed(
-            &system_instruction::allocate(&data_address, size.try_into().unwrap()),
-            &[accounts.user.clone(), accounts.system_program.clone()],
-            &[&[USER, &accounts.payer.key.to_bytes(), &[data_address_bump]]],
-        )?;
-
-        invoke_signed(
-            &system_instruction::assign(&data_address, program_id),
-            &[accounts.user.clone(), accounts.system_program.clone()],
-            &[&[USER, &accounts.payer.key.to_bytes(), &[data_address_bump]]],
-        )?;
-    }
+    let size = USER_LEN;
+
+    let required_lamports = rent
+        .minimum_balance(size)
+        .max(1)
+        .saturating_sub(accounts.user.lamports());
+
+    invoke(
+        &system_instruction::transfer(accounts.payer.key, &data_address, required_lamports),
+        &[
+            accounts.payer.clone(),
+            accounts.user.clone(),
+            accounts.system_program.clone(),
+        ],
+    )?;
+
+    invoke_signed(
+        &system_instruction::allocate(&data_address, size.try_into().unwrap()),
+        &[accounts.user.clone(), accounts.system_program.clone()],
+        &[&[USER, &accounts.payer.key.to_bytes(), &[data_address_bump]]],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(&data_address, program_id),
+        &[accounts.user.clone(), accounts.system_program.clone()],
+        &[&[USER, &accounts.payer.key.to_bytes(), &[data_address_bump]]],
+    )?;
 
-    user.serialize(&mut &mut accounts.user.data.borrow_mut()[..])?;
+    PodAccount::init(accounts.user, user)?;
+
+    BettingEvent::UserRegistered {
+        user: *accounts.payer.key,
+        referrer,
+    }
+    .emit()?;
 
     Ok(())
 }
 
+/// The wallet behind `referrer`, which must not be `user` itself.
+pub fn resolve_referrer(
+    referrer: Referrer,
+    referral_code: Option<&AccountInfo>,
+    program_id: &Pubkey,
+    user: &Pubkey,
+) -> Result<Pubkey, ProgramError> {
+    let referrer = match referrer {
+        Referrer::Wallet(wallet) => wallet,
+        Referrer::Code(code) => get_referral_code_owner(
+            referral_code.ok_or(ContractError::InvalidInstructionData)?,
+            program_id,
+            &code,
+        )?,
+    };
+
+    require(&referrer != user, ContractError::SelfReferral)?;
+
+    Ok(referrer)
+}
+
 #[allow(dead_code)]
 pub struct Accounts<'a, 'b> {
     pub payer: &'a AccountInfo<'b>,
     pub system_program: &'a AccountInfo<'b>,
     pub rent_info: &'a AccountInfo<'b>,
     pub user: &'a AccountInfo<'b>,
+    pub referral_code: Option<&'a AccountInfo<'b>>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -98,6 +137,7 @@ impl<'a, 'b> Accounts<'a, 'b> {
             system_program: next_account_info(acc_iter)?,
             rent_info: next_account_info(acc_iter)?,
             user: next_account_info(acc_iter)?,
+            referral_code: acc_iter.next(),
         })
     }
 }
@@ -0,0 +1,175 @@
+use crate::consts::{BETTING, DISCRIMINATOR_ACCOUNT_VERSION, RESERVED_SPACE, ROLE};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::PodBettingInfo;
+use crate::state::structs::{AccountType, Role};
+use borsh::BorshSerialize;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::sysvar::Sysvar;
+
+/// Passes for the admin and for any signer whose role PDA holds `permission`.
+pub fn check_permission(
+    betting_info: &PodBettingInfo,
+    payer: &AccountInfo,
+    role: Option<&AccountInfo>,
+    program_id: &Pubkey,
+    permission: u8,
+) -> ProgramResult {
+    if !payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    if *payer.key == betting_info.admin {
+        return Ok(());
+    }
+
+    let role = role.ok_or(ContractError::UnauthorisedAccess)?;
+
+    let role = ProgramAccount::<Role>::load(role, program_id, &[ROLE, &payer.key.to_bytes()])
+        .map_err(|_| ContractError::UnauthorisedAccess)?;
+
+    if role.permissions & permission == 0 {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    Ok(())
+}
+
+pub fn grant_role(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    address: Pubkey,
+    permissions: u8,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    if *accounts.payer.key != betting_info.admin || !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    if accounts.role.owner != program_id {
+        let (role_pda, role_bump) =
+            Pubkey::find_program_address(&[ROLE, &address.to_bytes()], program_id);
+
+        if *accounts.role.key != role_pda {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
+
+        let role = Role {
+            discriminator: AccountType::Role.discriminator(),
+            version: DISCRIMINATOR_ACCOUNT_VERSION,
+            address,
+            permissions: 0,
+            bump: role_bump,
+            reserved: [0; RESERVED_SPACE - 1],
+        };
+
+        let size = (role.try_to_vec()?).len() as u64;
+
+        let required_lamports = rent
+            .minimum_balance(size as usize)
+            .max(1)
+            .saturating_sub(accounts.role.lamports());
+
+        invoke(
+            &system_instruction::transfer(accounts.payer.key, &role_pda, required_lamports),
+            &[
+                accounts.payer.clone(),
+                accounts.role.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+
+        invoke_signed(
+            &system_instruction::allocate(&role_pda, size),
+            &[accounts.role.clone(), accounts.system_program.clone()],
+            &[&[ROLE, &address.to_bytes(), &[role_bump]]],
+        )?;
+
+        invoke_signed(
+            &system_instruction::assign(&role_pda, program_id),
+            &[accounts.role.clone(), accounts.system_program.clone()],
+            &[&[ROLE, &address.to_bytes(), &[role_bump]]],
+        )?;
+
+        role.serialize(&mut &mut accounts.role.data.borrow_mut()[..])?;
+    }
+
+    let mut role =
+        ProgramAccount::<Role>::load(accounts.role, program_id, &[ROLE, &address.to_bytes()])?;
+
+    role.permissions |= permissions;
+    role.save()?;
+
+    BettingEvent::RoleChanged {
+        address,
+        permissions: role.permissions,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+pub fn revoke_role(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    address: Pubkey,
+    permissions: u8,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    if *accounts.payer.key != betting_info.admin || !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    let mut role =
+        ProgramAccount::<Role>::load(accounts.role, program_id, &[ROLE, &address.to_bytes()])?;
+
+    role.permissions &= !permissions;
+    role.save()?;
+
+    BettingEvent::RoleChanged {
+        address,
+        permissions: role.permissions,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub role: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            role: next_account_info(acc_iter)?,
+        })
+    }
+}
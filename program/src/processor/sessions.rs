@@ -0,0 +1,221 @@
+use crate::consts::{DISCRIMINATOR_ACCOUNT_VERSION, RESERVED_SPACE, SESSION, USER};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::ban::check_not_banned;
+use crate::processor::bots::close_account;
+use crate::processor::require;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::PodUser;
+use crate::state::structs::{AccountType, Session};
+use borsh::BorshSerialize;
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::clock::Clock;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::sysvar::Sysvar;
+use std::convert::TryInto;
+
+/// Lets `delegate` open and join games for the user until `expires_at`. The session only
+/// authorises the games; the stakes are moved by the betting PDA, so the user also has to
+/// approve it as the SPL delegate of their token account. A new session replaces the old one.
+pub fn create_session(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    delegate: Pubkey,
+    expires_at: u64,
+    max_stake_per_game: u64,
+    max_total: u64,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    let user_info = PodAccount::<PodUser>::load(
+        accounts.user,
+        program_id,
+        &[USER, &accounts.payer.key.to_bytes()],
+    )?;
+
+    check_not_banned(&user_info)?;
+    require(
+        expires_at > Clock::get()?.unix_timestamp as u64,
+        ContractError::SessionExpired,
+    )?;
+
+    if accounts.session.owner == program_id {
+        let mut session_info = ProgramAccount::<Session>::load(
+            accounts.session,
+            program_id,
+            &[SESSION, &accounts.payer.key.to_bytes()],
+        )?;
+
+        session_info.delegate = delegate;
+        session_info.expires_at = expires_at;
+        session_info.max_stake_per_game = max_stake_per_game;
+        session_info.max_total = max_total;
+        session_info.spent = 0;
+        session_info.save()?;
+    } else {
+        let (session_pda, session_bump) =
+            Pubkey::find_program_address(&[SESSION, &accounts.payer.key.to_bytes()], program_id);
+
+        if *accounts.session.key != session_pda {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
+
+        let rent = &Rent::from_account_info(accounts.rent_info)?;
+
+        let session_info = Session {
+            discriminator: AccountType::Session.discriminator(),
+            version: DISCRIMINATOR_ACCOUNT_VERSION,
+            user: *accounts.payer.key,
+            delegate,
+            expires_at,
+            max_stake_per_game,
+            max_total,
+            spent: 0,
+            bump: session_bump,
+            reserved: [0; RESERVED_SPACE - 1],
+        };
+
+        let size = (session_info.try_to_vec()?).len();
+
+        let required_lamports = rent
+            .minimum_balance(size)
+            .max(1)
+            .saturating_sub(accounts.session.lamports());
+
+        invoke(
+            &system_instruction::transfer(accounts.payer.key, &session_pda, required_lamports),
+            &[
+                accounts.payer.clone(),
+                accounts.session.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+
+        invoke_signed(
+            &system_instruction::allocate(&session_pda, size.try_into().unwrap()),
+            &[accounts.session.clone(), accounts.system_program.clone()],
+            &[&[SESSION, &accounts.payer.key.to_bytes(), &[session_bump]]],
+        )?;
+
+        invoke_signed(
+            &system_instruction::assign(&session_pda, program_id),
+            &[accounts.session.clone(), accounts.system_program.clone()],
+            &[&[SESSION, &accounts.payer.key.to_bytes(), &[session_bump]]],
+        )?;
+
+        session_info.serialize(&mut &mut accounts.session.data.borrow_mut()[..])?;
+    }
+
+    BettingEvent::SessionCreated {
+        user: *accounts.payer.key,
+        delegate,
+        expires_at,
+        max_stake_per_game,
+        max_total,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Ends the session before it expires and refunds its rent to the user. The SPL approval is
+/// separate and is revoked on the token account.
+pub fn revoke_session(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    ProgramAccount::<Session>::load(
+        accounts.session,
+        program_id,
+        &[SESSION, &accounts.payer.key.to_bytes()],
+    )
+    .map_err(|_| ContractError::InvalidSession)?;
+
+    close_account(accounts.session, accounts.payer)?;
+
+    BettingEvent::SessionRevoked {
+        user: *accounts.payer.key,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Charges a game of `type_price` to the session of `user`, which `delegate` has to hold.
+pub fn use_session(
+    session: Option<&AccountInfo>,
+    program_id: &Pubkey,
+    delegate: &Pubkey,
+    user: &Pubkey,
+    type_price: u64,
+) -> ProgramResult {
+    let session = session.ok_or(ContractError::InvalidSession)?;
+
+    let mut session_info =
+        ProgramAccount::<Session>::load(session, program_id, &[SESSION, &user.to_bytes()])
+            .map_err(|_| ContractError::InvalidSession)?;
+
+    require(
+        session_info.delegate == *delegate,
+        ContractError::InvalidSession,
+    )?;
+    require(
+        (Clock::get()?.unix_timestamp as u64) < session_info.expires_at,
+        ContractError::SessionExpired,
+    )?;
+    require(
+        session_info.max_stake_per_game == 0 || type_price <= session_info.max_stake_per_game,
+        ContractError::SessionLimit,
+    )?;
+
+    let spent = session_info
+        .spent
+        .checked_add(type_price)
+        .ok_or(ContractError::OperationWithOverflow)?;
+
+    require(
+        session_info.max_total == 0 || spent <= session_info.max_total,
+        ContractError::SessionLimit,
+    )?;
+
+    session_info.spent = spent;
+    session_info.save()?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub user: &'a AccountInfo<'b>,
+    pub session: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            user: next_account_info(acc_iter)?,
+            session: next_account_info(acc_iter)?,
+        })
+    }
+}
@@ -1,7 +1,12 @@
-use crate::consts::{BETTING, TYPE_PRICE};
+use crate::consts::{
+    BETTING, DISCRIMINATOR_ACCOUNT_VERSION, FEE_ADMIN, RESERVED_SPACE, TYPE_PRICE,
+};
 use crate::error::ContractError;
-use crate::state::helpers::get_betting_info;
-use crate::state::structs::TypePrice;
+use crate::events::BettingEvent;
+use crate::processor::roles::check_permission;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::PodBettingInfo;
+use crate::state::structs::{AccountType, TypePrice};
 use borsh::BorshSerialize;
 use solana_program::account_info::{next_account_info, AccountInfo};
 use solana_program::entrypoint::ProgramResult;
@@ -22,27 +27,46 @@ pub fn set_type_price(
 
     let rent = &Rent::from_account_info(accounts.rent_info)?;
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
-
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        FEE_ADMIN,
+    )?;
+
+    let (paused, data_address_bump) = if accounts.type_price.owner == program_id {
+        let type_price_info = ProgramAccount::<TypePrice>::load(
+            accounts.type_price,
+            program_id,
+            &[TYPE_PRICE, t.to_string().as_bytes()],
+        )?;
 
-    let betting_info = get_betting_info(&accounts.pda.data.borrow())?;
+        (type_price_info.paused, type_price_info.bump)
+    } else {
+        let (data_address, data_address_bump) =
+            Pubkey::find_program_address(&[TYPE_PRICE, t.to_string().as_bytes()], program_id);
 
-    if *accounts.payer.key != betting_info.manager || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
-    }
+        if *accounts.type_price.key != data_address {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
 
-    let (data_address, data_address_bump) =
-        Pubkey::find_program_address(&[TYPE_PRICE, t.to_string().as_bytes()], program_id);
+        (false, data_address_bump)
+    };
 
-    if *accounts.type_price.key != data_address {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let type_price = TypePrice {
+        discriminator: AccountType::TypePrice.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        price,
+        paused,
+        bump: data_address_bump,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
 
     if accounts.type_price.owner != program_id {
-        let size: u64 = 8;
+        let size = (type_price.try_to_vec()?).len() as u64;
 
         let required_lamports = rent
             .minimum_balance(size as usize)
@@ -50,7 +74,11 @@ pub fn set_type_price(
             .saturating_sub(accounts.type_price.lamports());
 
         invoke(
-            &system_instruction::transfer(accounts.payer.key, &data_address, required_lamports),
+            &system_instruction::transfer(
+                accounts.payer.key,
+                accounts.type_price.key,
+                required_lamports,
+            ),
             &[
                 accounts.payer.clone(),
                 accounts.type_price.clone(),
@@ -59,21 +87,22 @@ pub fn set_type_price(
         )?;
 
         invoke_signed(
-            &system_instruction::allocate(&data_address, size),
+            &system_instruction::allocate(accounts.type_price.key, size),
             &[accounts.type_price.clone(), accounts.system_program.clone()],
             &[&[TYPE_PRICE, t.to_string().as_bytes(), &[data_address_bump]]],
         )?;
 
         invoke_signed(
-            &system_instruction::assign(&data_address, program_id),
+            &system_instruction::assign(accounts.type_price.key, program_id),
             &[accounts.type_price.clone(), accounts.system_program.clone()],
             &[&[TYPE_PRICE, t.to_string().as_bytes(), &[data_address_bump]]],
         )?;
     }
 
-    let type_price = TypePrice { price };
     type_price.serialize(&mut &mut accounts.type_price.data.borrow_mut()[..])?;
 
+    BettingEvent::TypePriceChanged { t, price }.emit()?;
+
     Ok(())
 }
 
@@ -84,6 +113,7 @@ pub struct Accounts<'a, 'b> {
     pub rent_info: &'a AccountInfo<'b>,
     pub pda: &'a AccountInfo<'b>,
     pub type_price: &'a AccountInfo<'b>,
+    pub role: Option<&'a AccountInfo<'b>>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -97,6 +127,7 @@ impl<'a, 'b> Accounts<'a, 'b> {
             rent_info: next_account_info(acc_iter)?,
             pda: next_account_info(acc_iter)?,
             type_price: next_account_info(acc_iter)?,
+            role: acc_iter.next(),
         })
     }
 }
@@ -1,196 +1,127 @@
-use crate::consts::{ADMIN, BETTING};
+use crate::consts::{BETTING, PAUSER, PAUSE_JOIN_GAME, PAUSE_NEW_GAME};
 use crate::error::ContractError;
-use crate::state::helpers::get_betting_info;
-use borsh::BorshSerialize;
+use crate::events::BettingEvent;
+use crate::processor::require;
+use crate::processor::roles::check_permission;
+use crate::state::accounts::PodAccount;
+use crate::state::pod::PodBettingInfo;
 use solana_program::account_info::{next_account_info, AccountInfo};
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
 
-pub fn change_close_delay(
-    accounts: &[AccountInfo],
-    program_id: &Pubkey,
-    new_delay: u64,
-) -> ProgramResult {
+pub fn lock_bets(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
+    let mut betting_info =
+        PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        PAUSER,
+    )?;
 
-    let admin = ADMIN.parse::<Pubkey>().unwrap();
+    betting_info.pause_flags |= PAUSE_NEW_GAME | PAUSE_JOIN_GAME;
 
-    if *accounts.payer.key != admin || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
+    BettingEvent::PauseFlagsChanged {
+        flags: betting_info.pause_flags,
     }
-
-    let mut betting_info = get_betting_info(&accounts.pda.data.borrow())?;
-
-    betting_info.close_delay = new_delay;
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
+    .emit()?;
 
     Ok(())
 }
 
-pub fn lock_bets(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+pub fn unlock_bets(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
+    let mut betting_info =
+        PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        PAUSER,
+    )?;
 
-    let admin = ADMIN.parse::<Pubkey>().unwrap();
+    betting_info.pause_flags &= !(PAUSE_NEW_GAME | PAUSE_JOIN_GAME);
 
-    if *accounts.payer.key != admin || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
+    BettingEvent::PauseFlagsChanged {
+        flags: betting_info.pause_flags,
     }
-
-    let mut betting_info = get_betting_info(&accounts.pda.data.borrow())?;
-
-    betting_info.accept_bets = false;
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
+    .emit()?;
 
     Ok(())
 }
 
-pub fn unlock_bets(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+pub fn set_pause_flags(accounts: &[AccountInfo], program_id: &Pubkey, flags: u8) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
+    let mut betting_info =
+        PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        accounts.role,
+        program_id,
+        PAUSER,
+    )?;
 
-    let admin = ADMIN.parse::<Pubkey>().unwrap();
+    betting_info.pause_flags = flags;
 
-    if *accounts.payer.key != admin || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
-    }
-
-    let mut betting_info = get_betting_info(&accounts.pda.data.borrow())?;
-
-    betting_info.accept_bets = true;
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
+    BettingEvent::PauseFlagsChanged { flags }.emit()?;
 
     Ok(())
 }
 
-pub fn new_manager(
+pub fn propose_admin(
     accounts: &[AccountInfo],
     program_id: &Pubkey,
-    manager: Pubkey,
+    admin: Pubkey,
 ) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
-
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let admin = ADMIN.parse::<Pubkey>().unwrap();
+    let mut betting_info =
+        PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    if *accounts.payer.key != admin || !accounts.payer.is_signer {
+    if *accounts.payer.key != betting_info.admin || !accounts.payer.is_signer {
         return Err(ContractError::UnauthorisedAccess.into());
     }
 
-    let mut betting_info = get_betting_info(&accounts.pda.data.borrow())?;
+    betting_info.pending_admin = admin;
 
-    betting_info.manager = manager;
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
+    BettingEvent::AdminProposed { admin }.emit()?;
 
     Ok(())
 }
 
-pub fn set_global_fee(accounts: &[AccountInfo], program_id: &Pubkey, fee: u64) -> ProgramResult {
+pub fn accept_admin(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
     let accounts = Accounts::new(accounts)?;
 
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
-
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
+    let mut betting_info =
+        PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
 
-    let mut betting_info = get_betting_info(&accounts.pda.data.borrow())?;
+    require(
+        betting_info.pending_admin != Pubkey::default(),
+        ContractError::NoAdminProposed,
+    )?;
 
-    if *accounts.payer.key != betting_info.manager || !accounts.payer.is_signer {
+    if *accounts.payer.key != betting_info.pending_admin || !accounts.payer.is_signer {
         return Err(ContractError::UnauthorisedAccess.into());
     }
 
-    betting_info.global_fee = fee;
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
+    betting_info.admin = betting_info.pending_admin;
+    betting_info.pending_admin = Pubkey::default();
 
-    Ok(())
-}
-
-pub fn set_admin_fee(accounts: &[AccountInfo], program_id: &Pubkey, fee: u64) -> ProgramResult {
-    let accounts = Accounts::new(accounts)?;
-
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
-
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
+    BettingEvent::AdminChanged {
+        admin: betting_info.admin,
     }
-
-    let mut betting_info = get_betting_info(&accounts.pda.data.borrow())?;
-
-    if *accounts.payer.key != betting_info.manager || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
-    }
-
-    betting_info.admin_fee = fee;
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
-
-    Ok(())
-}
-
-pub fn set_winner_fee(accounts: &[AccountInfo], program_id: &Pubkey, fee: u64) -> ProgramResult {
-    let accounts = Accounts::new(accounts)?;
-
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
-
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let mut betting_info = get_betting_info(&accounts.pda.data.borrow())?;
-
-    if *accounts.payer.key != betting_info.manager || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
-    }
-
-    betting_info.referrer_fee = fee;
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
-
-    Ok(())
-}
-
-pub fn set_transaction_fee(
-    accounts: &[AccountInfo],
-    program_id: &Pubkey,
-    fee: u64,
-) -> ProgramResult {
-    let accounts = Accounts::new(accounts)?;
-
-    let (betting_pda, _) = Pubkey::find_program_address(&[BETTING], program_id);
-
-    if accounts.pda.key != &betting_pda {
-        return Err(ContractError::InvalidInstructionData.into());
-    }
-
-    let mut betting_info = get_betting_info(&accounts.pda.data.borrow())?;
-
-    if *accounts.payer.key != betting_info.manager || !accounts.payer.is_signer {
-        return Err(ContractError::UnauthorisedAccess.into());
-    }
-
-    betting_info.transaction_fee = fee;
-    betting_info.serialize(&mut &mut accounts.pda.data.borrow_mut()[..])?;
+    .emit()?;
 
     Ok(())
 }
@@ -200,6 +131,7 @@ pub struct Accounts<'a, 'b> {
     pub payer: &'a AccountInfo<'b>,
     pub system_program: &'a AccountInfo<'b>,
     pub pda: &'a AccountInfo<'b>,
+    pub role: Option<&'a AccountInfo<'b>>,
 }
 
 impl<'a, 'b> Accounts<'a, 'b> {
@@ -211,6 +143,7 @@ impl<'a, 'b> Accounts<'a, 'b> {
             payer: next_account_info(acc_iter)?,
             system_program: next_account_info(acc_iter)?,
             pda: next_account_info(acc_iter)?,
+            role: acc_iter.next(),
         })
     }
 }
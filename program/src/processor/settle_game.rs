@@ -0,0 +1,474 @@
+use crate::consts::{
+    BETTING, GAME, PAUSE_SETTLE, PAYOUT_ADMIN, PAYOUT_REFERRERS, PAYOUT_WINNER, PAYOUT_WINNER2,
+    RESOLVER, STATS, TYPE_PRICE, USER, WHITELIST, WINNER_GAMER1, WINNER_GAMER2,
+};
+use crate::error::ContractError;
+use crate::events::BettingEvent;
+use crate::processor::referral_rewards::{accrue_referral_chain, get_referrer, ReferralChain};
+use crate::processor::require;
+use crate::processor::roles::check_permission;
+use crate::processor::user_stats::record_result;
+use crate::state::accounts::{PodAccount, ProgramAccount};
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use crate::state::structs::{SupportedToken, TokenStats, TypePrice};
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+
+/// Records the winner and the fees on the game without moving any tokens; the transfers are
+/// made one by one with `distribute`. The referral fee is the whole referral pool until the
+/// referral payout walks the referral chains.
+pub fn settle(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    user: Pubkey,
+    winner_address: Pubkey,
+    t: u64,
+) -> ProgramResult {
+    let accounts = Accounts::new(accounts)?;
+
+    if !accounts.payer.is_signer {
+        return Err(ContractError::UnauthorisedAccess.into());
+    }
+
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    check_permission(
+        &betting_info,
+        accounts.payer,
+        Some(accounts.role),
+        program_id,
+        RESOLVER,
+    )?;
+
+    require(!betting_info.shutdown.get(), ContractError::Shutdown)?;
+
+    require(
+        betting_info.pause_flags & PAUSE_SETTLE == 0,
+        ContractError::SettlementPaused,
+    )?;
+
+    let mut game_info =
+        PodAccount::<PodGame>::load(accounts.game, program_id, &[GAME, &user.to_bytes()])?;
+
+    require(!game_info.closed.get(), ContractError::GameClosed)?;
+    require(
+        winner_address == game_info.gamer1 || winner_address == game_info.gamer2,
+        ContractError::InvalidWinner,
+    )?;
+
+    let mut user_info =
+        PodAccount::<PodUser>::load(accounts.user, program_id, &[USER, &user.to_bytes()])?;
+
+    let mut user2_info = PodAccount::<PodUser>::load(
+        accounts.user2,
+        program_id,
+        &[USER, &game_info.gamer2.to_bytes()],
+    )?;
+
+    let type_price_info = ProgramAccount::<TypePrice>::load(
+        accounts.type_price,
+        program_id,
+        &[TYPE_PRICE, t.to_string().as_bytes()],
+    )?;
+
+    require(
+        type_price_info.price == game_info.type_price,
+        ContractError::TypePriceMismatch,
+    )?;
+    require(!type_price_info.paused, ContractError::TypePaused)?;
+
+    let supported_token_info = ProgramAccount::<SupportedToken>::load(
+        accounts.supported_token,
+        program_id,
+        &[WHITELIST, &game_info.token1.to_bytes()],
+    )?;
+
+    let supported_token1_info = ProgramAccount::<SupportedToken>::load(
+        accounts.supported_token1,
+        program_id,
+        &[WHITELIST, &game_info.token2.to_bytes()],
+    )?;
+
+    require(
+        !supported_token_info.paused && !supported_token1_info.paused,
+        ContractError::TokenPaused,
+    )?;
+
+    let gamer1_won = winner_address == game_info.gamer1;
+    let price_fee = game_info.type_price * betting_info.global_fee / 100 * 2;
+
+    user_info.in_game = false.into();
+    user_info.active_games = user_info.active_games.saturating_sub(1);
+    user_info.turnover += game_info.type_price;
+    record_result(&mut user_info, gamer1_won, game_info.type_price, price_fee)?;
+
+    user2_info.in_game = false.into();
+    user2_info.active_games = user2_info.active_games.saturating_sub(1);
+    user2_info.turnover += game_info.type_price;
+    record_result(
+        &mut user2_info,
+        !gamer1_won,
+        game_info.type_price,
+        price_fee,
+    )?;
+
+    let fee = game_info.amount1 * betting_info.global_fee / 100 * 2;
+    let referrer_pool = fee * (betting_info.referrer_fee / 2) / 100;
+
+    game_info.closed = true.into();
+    game_info.winner = if gamer1_won {
+        WINNER_GAMER1
+    } else {
+        WINNER_GAMER2
+    };
+    game_info.admin_fee = fee * betting_info.admin_fee / 100;
+    game_info.referrer_fee = referrer_pool * 2;
+    game_info.distributed = 0;
+
+    let mut stats_info = ProgramAccount::<TokenStats>::load(
+        accounts.stats,
+        program_id,
+        &[STATS, &game_info.token1.to_bytes()],
+    )?;
+
+    stats_info.games_settled += 1;
+    stats_info.save()?;
+
+    if game_info.token2 != game_info.token1 {
+        let mut stats1_info = ProgramAccount::<TokenStats>::load(
+            accounts.stats1,
+            program_id,
+            &[STATS, &game_info.token2.to_bytes()],
+        )?;
+
+        stats1_info.games_settled += 1;
+        stats1_info.save()?;
+    }
+
+    BettingEvent::GameSettled {
+        game: *accounts.game.key,
+        winner: winner_address,
+        token1: game_info.token1,
+        amount1: game_info.amount1,
+        token2: game_info.token2,
+        amount2: game_info.amount2,
+        admin_fee: game_info.admin_fee,
+        referrer_fee: game_info.referrer_fee,
+        payout1: winner_payout(&game_info)?,
+        payout2: game_info.amount2,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// Makes one `PAYOUT_*` transfer of a settled game out of its token account, creating the
+/// recipient's token account when it's missing. Anyone can push the payouts; the player can't
+/// start a new game until all of them are out. The referral payout goes first, as it decides
+/// how much of the referral pool falls back to the admin.
+pub fn distribute(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    user: Pubkey,
+    index: u8,
+) -> ProgramResult {
+    let accounts = DistributeAccounts::new(accounts)?;
+
+    if *accounts.token_program.key != spl_token::id() {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    let betting_info = PodAccount::<PodBettingInfo>::load(accounts.pda, program_id, &[BETTING])?;
+
+    let mut game_info =
+        PodAccount::<PodGame>::load(accounts.game, program_id, &[GAME, &user.to_bytes()])?;
+    let game_bump = game_info.bump;
+
+    require(game_info.winner != 0, ContractError::GameNotSettled)?;
+    require(
+        index <= PAYOUT_WINNER2,
+        ContractError::InvalidInstructionData,
+    )?;
+    require(
+        game_info.distributed & (1 << index) == 0,
+        ContractError::PayoutDistributed,
+    )?;
+    require(
+        index != PAYOUT_ADMIN || game_info.distributed & (1 << PAYOUT_REFERRERS) != 0,
+        ContractError::ReferrerPayoutPending,
+    )?;
+
+    if index == PAYOUT_REFERRERS {
+        accrue_referrer_fee(&accounts, program_id, &betting_info, &mut game_info)?;
+    }
+
+    let winner = if game_info.winner == WINNER_GAMER1 {
+        game_info.gamer1
+    } else {
+        game_info.gamer2
+    };
+
+    let (recipient, token, amount) = match index {
+        PAYOUT_ADMIN => (betting_info.admin, game_info.token1, game_info.admin_fee),
+        PAYOUT_REFERRERS => (*accounts.pda.key, game_info.token1, game_info.referrer_fee),
+        PAYOUT_WINNER => (winner, game_info.token1, winner_payout(&game_info)?),
+        _ => (winner, game_info.token2, game_info.amount2),
+    };
+
+    if *accounts.recipient.key != recipient || *accounts.token.key != token {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    if spl_associated_token_account::get_associated_token_address(accounts.game.key, &token)
+        != *accounts.source.key
+    {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    if spl_associated_token_account::get_associated_token_address(&recipient, &token)
+        != *accounts.destination.key
+    {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    game_info.distributed |= 1 << index;
+
+    // the transfer passes the game on, and the referral payout the betting PDA as the recipient
+    drop(game_info);
+    drop(betting_info);
+
+    if amount != 0 {
+        if accounts.destination.owner != accounts.token_program.key {
+            invoke(
+                &spl_associated_token_account::create_associated_token_account(
+                    accounts.payer.key,
+                    accounts.recipient.key,
+                    accounts.token.key,
+                ),
+                &[
+                    accounts.payer.clone(),
+                    accounts.destination.clone(),
+                    accounts.recipient.clone(),
+                    accounts.token.clone(),
+                    accounts.system_program.clone(),
+                    accounts.token_program.clone(),
+                    accounts.rent_info.clone(),
+                    accounts.token_assoc.clone(),
+                ],
+            )?;
+        }
+
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                accounts.token_program.key,
+                accounts.source.key,
+                accounts.destination.key,
+                accounts.game.key,
+                &[],
+                amount,
+            )?,
+            &[
+                accounts.source.clone(),
+                accounts.destination.clone(),
+                accounts.game.clone(),
+                accounts.token_program.clone(),
+            ],
+            &[&[GAME, &user.to_bytes(), &[game_bump]]],
+        )?;
+    }
+
+    BettingEvent::PayoutDistributed {
+        game: *accounts.game.key,
+        index,
+        recipient,
+        token,
+        amount,
+    }
+    .emit()?;
+
+    Ok(())
+}
+
+/// The winner's share of the first stake, what's left of it after the fees.
+fn winner_payout(game_info: &PodGame) -> Result<u64, ProgramError> {
+    game_info
+        .amount1
+        .checked_sub(game_info.admin_fee)
+        .and_then(|payout| payout.checked_sub(game_info.referrer_fee))
+        .ok_or_else(|| ContractError::OperationWithOverflow.into())
+}
+
+/// Accrues the referral rewards of both gamers out of the referral pool recorded by `settle`,
+/// opening the reward PDAs that don't exist yet at the payer's expense. Only what was accrued
+/// goes to the vault; every tier the referral chains did not reach stays with the admin.
+fn accrue_referrer_fee(
+    accounts: &DistributeAccounts,
+    program_id: &Pubkey,
+    betting_info: &PodBettingInfo,
+    game_info: &mut PodGame,
+) -> ProgramResult {
+    let (payer, system_program) = (accounts.payer, accounts.system_program);
+    let rent = &Rent::from_account_info(accounts.rent_info)?;
+    let accounts = ReferralAccounts::new(accounts.referral)?;
+
+    let referrer_pool = game_info.referrer_fee / 2;
+    let chain = &mut accounts.referral_chain.iter();
+    let mut referrer_paid = 0;
+
+    for (player, user, reward) in [
+        (game_info.gamer1, accounts.user, accounts.referrer_reward),
+        (game_info.gamer2, accounts.user2, accounts.referrer1_reward),
+    ]
+    .iter()
+    {
+        referrer_paid += accrue_referral_chain(
+            payer,
+            system_program,
+            rent,
+            reward,
+            chain,
+            program_id,
+            &ReferralChain {
+                player: *player,
+                // a player who deregistered since the game has no referrer
+                referrer: get_referrer(user, program_id, player)?.unwrap_or_default(),
+                mint: game_info.token1,
+                pool: referrer_pool,
+                tiers: betting_info.referral_tiers,
+            },
+        )?;
+    }
+
+    game_info.admin_fee = game_info
+        .referrer_fee
+        .checked_sub(referrer_paid)
+        .and_then(|unpaid| game_info.admin_fee.checked_add(unpaid))
+        .ok_or(ContractError::OperationWithOverflow)?;
+    game_info.referrer_fee = referrer_paid;
+
+    let mut stats_info = ProgramAccount::<TokenStats>::load(
+        accounts.stats,
+        program_id,
+        &[STATS, &game_info.token1.to_bytes()],
+    )?;
+
+    stats_info.admin_fees += game_info.admin_fee;
+    stats_info.referrer_fees += referrer_paid;
+    stats_info.save()?;
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub struct Accounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub supported_token: &'a AccountInfo<'b>,
+    pub supported_token1: &'a AccountInfo<'b>,
+    pub user: &'a AccountInfo<'b>,
+    pub user2: &'a AccountInfo<'b>,
+    pub game: &'a AccountInfo<'b>,
+    pub type_price: &'a AccountInfo<'b>,
+    pub stats: &'a AccountInfo<'b>,
+    pub stats1: &'a AccountInfo<'b>,
+    pub role: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> Accounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<Accounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(Accounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            supported_token: next_account_info(acc_iter)?,
+            supported_token1: next_account_info(acc_iter)?,
+            user: next_account_info(acc_iter)?,
+            user2: next_account_info(acc_iter)?,
+            game: next_account_info(acc_iter)?,
+            type_price: next_account_info(acc_iter)?,
+            stats: next_account_info(acc_iter)?,
+            stats1: next_account_info(acc_iter)?,
+            role: next_account_info(acc_iter)?,
+        })
+    }
+}
+
+#[allow(dead_code)]
+pub struct DistributeAccounts<'a, 'b> {
+    pub payer: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda: &'a AccountInfo<'b>,
+    pub rent_info: &'a AccountInfo<'b>,
+    pub game: &'a AccountInfo<'b>,
+    pub source: &'a AccountInfo<'b>,
+    pub recipient: &'a AccountInfo<'b>,
+    pub destination: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+    pub token: &'a AccountInfo<'b>,
+    pub token_assoc: &'a AccountInfo<'b>,
+    /// The `ReferralAccounts` of the referral payout; empty for the other payouts.
+    pub referral: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> DistributeAccounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<DistributeAccounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(DistributeAccounts {
+            payer: next_account_info(acc_iter)?,
+            system_program: next_account_info(acc_iter)?,
+            pda: next_account_info(acc_iter)?,
+            rent_info: next_account_info(acc_iter)?,
+            game: next_account_info(acc_iter)?,
+            source: next_account_info(acc_iter)?,
+            recipient: next_account_info(acc_iter)?,
+            destination: next_account_info(acc_iter)?,
+            token_program: next_account_info(acc_iter)?,
+            token: next_account_info(acc_iter)?,
+            token_assoc: next_account_info(acc_iter)?,
+            referral: acc_iter.as_slice(),
+        })
+    }
+}
+
+/// The stats of the first token and the User PDAs of both gamers, followed by the reward PDA of
+/// the direct referrer of each gamer and then their referral chains.
+#[allow(dead_code)]
+pub struct ReferralAccounts<'a, 'b> {
+    pub stats: &'a AccountInfo<'b>,
+    pub user: &'a AccountInfo<'b>,
+    pub user2: &'a AccountInfo<'b>,
+    pub referrer_reward: &'a AccountInfo<'b>,
+    pub referrer1_reward: &'a AccountInfo<'b>,
+    pub referral_chain: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> ReferralAccounts<'a, 'b> {
+    #[allow(dead_code)]
+    pub fn new(accounts: &'a [AccountInfo<'b>]) -> Result<ReferralAccounts<'a, 'b>, ProgramError> {
+        let acc_iter = &mut accounts.iter();
+
+        Ok(ReferralAccounts {
+            stats: next_account_info(acc_iter)?,
+            user: next_account_info(acc_iter)?,
+            user2: next_account_info(acc_iter)?,
+            referrer_reward: next_account_info(acc_iter)?,
+            referrer1_reward: next_account_info(acc_iter)?,
+            referral_chain: acc_iter.as_slice(),
+        })
+    }
+}
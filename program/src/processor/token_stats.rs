@@ -0,0 +1,73 @@
+use crate::consts::{DISCRIMINATOR_ACCOUNT_VERSION, RESERVED_SPACE, STATS};
+use crate::error::ContractError;
+use crate::state::structs::{AccountType, TokenStats};
+use borsh::BorshSerialize;
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_instruction;
+
+/// Creates the stats PDA of `mint` on the first bet in that token. An existing account is left
+/// to the caller's `ProgramAccount::load`, which checks it against its stored bump.
+pub fn create_token_stats<'a>(
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    stats: &AccountInfo<'a>,
+    rent: &Rent,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> ProgramResult {
+    if stats.owner == program_id {
+        return Ok(());
+    }
+
+    let (stats_pda, stats_bump) =
+        Pubkey::find_program_address(&[STATS, &mint.to_bytes()], program_id);
+
+    if *stats.key != stats_pda {
+        return Err(ContractError::InvalidInstructionData.into());
+    }
+
+    let stats_info = TokenStats {
+        discriminator: AccountType::TokenStats.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        mint: *mint,
+        staked_volume: 0,
+        games_settled: 0,
+        admin_fees: 0,
+        referrer_fees: 0,
+        penalties: 0,
+        bump: stats_bump,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+
+    let size = (stats_info.try_to_vec()?).len() as u64;
+
+    let required_lamports = rent
+        .minimum_balance(size as usize)
+        .max(1)
+        .saturating_sub(stats.lamports());
+
+    invoke(
+        &system_instruction::transfer(payer.key, &stats_pda, required_lamports),
+        &[payer.clone(), stats.clone(), system_program.clone()],
+    )?;
+
+    invoke_signed(
+        &system_instruction::allocate(&stats_pda, size),
+        &[stats.clone(), system_program.clone()],
+        &[&[STATS, &mint.to_bytes(), &[stats_bump]]],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(&stats_pda, program_id),
+        &[stats.clone(), system_program.clone()],
+        &[&[STATS, &mint.to_bytes(), &[stats_bump]]],
+    )?;
+
+    stats_info.serialize(&mut &mut stats.data.borrow_mut()[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::error::ContractError;
+use crate::processor::limits::record_loss;
+use crate::state::pod::PodUser;
+use solana_program::clock::Clock;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::sysvar::Sysvar;
+
+/// Books a settled game of type price `type_price` for one player. The winner gains the other
+/// stake less `fee`, the loser loses its own stake.
+pub fn record_result(
+    user_info: &mut PodUser,
+    won: bool,
+    type_price: u64,
+    fee: u64,
+) -> ProgramResult {
+    let profit = if won {
+        type_price.saturating_sub(fee) as i64
+    } else {
+        -(type_price as i64)
+    };
+
+    user_info.games_played += 1;
+
+    if won {
+        user_info.wins += 1;
+    } else {
+        user_info.losses += 1;
+        record_loss(user_info, type_price)?;
+    }
+
+    add_profit(user_info, profit)
+}
+
+/// Books a game closed or refunded without a result; `penalty` is what the player paid for
+/// closing it.
+pub fn record_cancellation(user_info: &mut PodUser, penalty: u64) -> ProgramResult {
+    user_info.cancellations += 1;
+
+    add_profit(user_info, -(penalty as i64))
+}
+
+fn add_profit(user_info: &mut PodUser, profit: i64) -> ProgramResult {
+    user_info.net_profit = user_info
+        .net_profit
+        .checked_add(profit)
+        .ok_or(ContractError::OperationWithOverflow)?;
+    user_info.last_game_at = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -0,0 +1,447 @@
+use crate::error::ContractError;
+use crate::state::helpers::{
+    get_account_version, get_bot_info, get_bot_registry_info, get_latest_version,
+    get_pending_config_info, get_referral_code_info, get_referral_reward_info, get_role_info,
+    get_session_info, get_supported_token_info, get_token_stats_info, get_type_price_info,
+};
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use crate::state::structs::{
+    AccountType, BotInfo, BotRegistry, PendingConfig, ReferralCode, ReferralReward, Role, Session,
+    SupportedToken, TokenStats, TypePrice,
+};
+use borsh::BorshSerialize;
+use bytemuck::Pod;
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use std::cell::{Ref, RefMut};
+use std::ops::{Deref, DerefMut};
+
+/// Account data that checks its own `AccountType` header when unpacked.
+/// `bump` is the canonical bump of the account's PDA, stored when the account is created.
+pub trait AccountData: Sized {
+    const ACCOUNT_TYPE: AccountType;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError>;
+    fn pack(&self, data: &mut [u8]) -> ProgramResult;
+    fn bump(&self) -> u8;
+    fn set_bump(&mut self, bump: u8);
+}
+
+/// An account owned by the program at the expected PDA, holding a `T`. `version` is the layout
+/// the account is stored in; accounts in an older layout can be read, but have to be migrated
+/// with `MigrateAccount` before they can be saved.
+pub struct ProgramAccount<'a, 'b, T> {
+    pub info: &'a AccountInfo<'b>,
+    pub data: T,
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl<'a, 'b, T: AccountData> ProgramAccount<'a, 'b, T> {
+    pub fn load(
+        info: &'a AccountInfo<'b>,
+        program_id: &Pubkey,
+        seeds: &[&[u8]],
+    ) -> Result<ProgramAccount<'a, 'b, T>, ProgramError> {
+        if info.owner != program_id {
+            return Err(ContractError::InvalidAccountOwner.into());
+        }
+
+        let version = get_account_version(&info.data.borrow(), T::ACCOUNT_TYPE)?;
+        let mut data = T::unpack(&info.data.borrow())?;
+
+        let bump_seed = [data.bump()];
+        let mut bump_seeds = seeds.to_vec();
+        bump_seeds.push(&bump_seed);
+
+        let bump = match stored_address(&bump_seeds, program_id) {
+            Some(address) if address == *info.key => data.bump(),
+            _ => {
+                // accounts created before the bump was stored; the next save stores it
+                let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+
+                if *info.key != address {
+                    return Err(ContractError::InvalidInstructionData.into());
+                }
+
+                data.set_bump(bump);
+                bump
+            }
+        };
+
+        Ok(ProgramAccount {
+            info,
+            data,
+            bump,
+            version,
+        })
+    }
+
+    pub fn save(&self) -> ProgramResult {
+        if self.version != get_latest_version(T::ACCOUNT_TYPE) {
+            return Err(ContractError::MigrationRequired.into());
+        }
+
+        self.data.pack(&mut self.info.data.borrow_mut())
+    }
+}
+
+/// A `Pod` layout that starts with the header of its `AccountType`.
+pub trait PodData: Pod {
+    const ACCOUNT_TYPE: AccountType;
+
+    fn bump(&self) -> u8;
+}
+
+/// An account owned by the program at the expected PDA, viewed in place as a `T`: fields are read
+/// and written straight in the account data, without decoding or saving the account. Only
+/// accounts in the latest layout can be loaded, older ones have to be migrated first.
+///
+/// The account data stays borrowed until the view is dropped, so drop it before passing the
+/// account to a CPI.
+pub struct PodAccount<'a, 'b, T> {
+    pub info: &'a AccountInfo<'b>,
+    data: RefMut<'a, T>,
+}
+
+impl<'a, 'b, T: PodData> PodAccount<'a, 'b, T> {
+    pub fn load(
+        info: &'a AccountInfo<'b>,
+        program_id: &Pubkey,
+        seeds: &[&[u8]],
+    ) -> Result<PodAccount<'a, 'b, T>, ProgramError> {
+        if info.owner != program_id {
+            return Err(ContractError::InvalidAccountOwner.into());
+        }
+
+        if get_account_version(&info.data.borrow(), T::ACCOUNT_TYPE)?
+            != get_latest_version(T::ACCOUNT_TYPE)
+        {
+            return Err(ContractError::MigrationRequired.into());
+        }
+
+        let account = PodAccount::<T>::view(info)?;
+
+        let bump_seed = [account.bump()];
+        let mut bump_seeds = seeds.to_vec();
+        bump_seeds.push(&bump_seed);
+
+        if stored_address(&bump_seeds, program_id) != Some(*info.key)
+            && Pubkey::find_program_address(seeds, program_id).0 != *info.key
+        {
+            return Err(ContractError::InvalidInstructionData.into());
+        }
+
+        Ok(account)
+    }
+
+    /// Writes `value` to an account the caller just created at its PDA and views it.
+    pub fn init(
+        info: &'a AccountInfo<'b>,
+        value: T,
+    ) -> Result<PodAccount<'a, 'b, T>, ProgramError> {
+        let mut account = PodAccount::<T>::view(info)?;
+        *account = value;
+
+        Ok(account)
+    }
+
+    /// Borrows the account data as a `T`; it has to be exactly as long as `T` and aligned for it.
+    fn view(info: &'a AccountInfo<'b>) -> Result<PodAccount<'a, 'b, T>, ProgramError> {
+        let mut data = info.try_borrow_mut_data()?;
+
+        if bytemuck::try_from_bytes_mut::<T>(&mut data[..]).is_err() {
+            return Err(ContractError::DeserializeError.into());
+        }
+
+        Ok(PodAccount {
+            info,
+            data: RefMut::map(data, |data| bytemuck::from_bytes_mut(&mut data[..])),
+        })
+    }
+}
+
+impl<'a, 'b, T> Deref for PodAccount<'a, 'b, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<'a, 'b, T> DerefMut for PodAccount<'a, 'b, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+impl PodData for PodBettingInfo {
+    const ACCOUNT_TYPE: AccountType = AccountType::BettingInfo;
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl PodData for PodGame {
+    const ACCOUNT_TYPE: AccountType = AccountType::Game;
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl PodData for PodUser {
+    const ACCOUNT_TYPE: AccountType = AccountType::User;
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+/// The address `seeds` give with the stored bump. Builds with `derive-bumps` ignore the bump and
+/// derive every address, as the program did before bumps were stored; `tests/compute_units.rs`
+/// measures against them.
+fn stored_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<Pubkey> {
+    if cfg!(feature = "derive-bumps") {
+        return None;
+    }
+
+    Pubkey::create_program_address(seeds, program_id).ok()
+}
+
+impl<'a, 'b, T> Deref for ProgramAccount<'a, 'b, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<'a, 'b, T> DerefMut for ProgramAccount<'a, 'b, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+impl AccountData for SupportedToken {
+    const ACCOUNT_TYPE: AccountType = AccountType::SupportedToken;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_supported_token_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for TypePrice {
+    const ACCOUNT_TYPE: AccountType = AccountType::TypePrice;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_type_price_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for TokenStats {
+    const ACCOUNT_TYPE: AccountType = AccountType::TokenStats;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_token_stats_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for ReferralCode {
+    const ACCOUNT_TYPE: AccountType = AccountType::ReferralCode;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_referral_code_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for ReferralReward {
+    const ACCOUNT_TYPE: AccountType = AccountType::ReferralReward;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_referral_reward_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for Role {
+    const ACCOUNT_TYPE: AccountType = AccountType::Role;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_role_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for PendingConfig {
+    const ACCOUNT_TYPE: AccountType = AccountType::PendingConfig;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_pending_config_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for BotInfo {
+    const ACCOUNT_TYPE: AccountType = AccountType::BotInfo;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_bot_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for BotRegistry {
+    const ACCOUNT_TYPE: AccountType = AccountType::BotRegistry;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_bot_registry_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
+
+impl AccountData for Session {
+    const ACCOUNT_TYPE: AccountType = AccountType::Session;
+
+    fn unpack(data: &Ref<&mut [u8]>) -> Result<Self, ProgramError> {
+        get_session_info(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut &mut data[..])?;
+
+        Ok(())
+    }
+
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+}
@@ -1,45 +1,252 @@
+use crate::consts::{
+    ACCOUNT_VERSION, DISCRIMINATOR_ACCOUNT_VERSION, DISCRIMINATOR_LEN, POD_ACCOUNT_VERSION,
+    PROFILE_ACCOUNT_VERSION, STATS_ACCOUNT_VERSION, USER_ACCOUNT_VERSION,
+};
 use crate::error::ContractError;
-use crate::state::structs::{BettingInfo, Game, SupportedToken, TypePrice, User};
+use crate::state::legacy::{
+    user_v0_len, BettingInfoV0, BettingInfoV1, GameV0, GameV1, SupportedTokenV0, TypePriceV0,
+    UserV0, UserV1, UserV2, UserV3, UserV4, BETTING_INFO_V0_LEN, GAME_V0_LEN,
+    SUPPORTED_TOKEN_V0_LEN, TYPE_PRICE_V0_LEN,
+};
+use crate::state::pod::{PodBettingInfo, PodGame, PodUser};
+use crate::state::structs::{
+    AccountType, BettingInfo, BotInfo, BotRegistry, Game, PendingConfig, ReferralCode,
+    ReferralReward, Role, Session, SupportedToken, TokenStats, TypePrice, User,
+};
 use borsh::BorshDeserialize;
+use bytemuck::{bytes_of, Pod};
+use solana_program::entrypoint::ProgramResult;
 use solana_program::program_error::ProgramError;
-use std::cell::Ref;
+use std::mem::size_of;
 
-pub fn get_betting_info(data: &Ref<&mut [u8]>) -> Result<BettingInfo, ProgramError> {
-    if let Ok(data) = BettingInfo::try_from_slice(data) {
-        Ok(data)
+pub const GAME_LEN: usize = size_of::<PodGame>();
+
+pub const USER_LEN: usize = size_of::<PodUser>();
+
+pub const BETTING_INFO_LEN: usize = size_of::<PodBettingInfo>();
+
+/// Accounts start with the discriminator of `account_type` followed by their version. Accounts
+/// written before `DISCRIMINATOR_ACCOUNT_VERSION` start with the one-byte `AccountType` tag
+/// instead, and accounts written before versioning have no header and report version 0. Those
+/// are only recognised at the exact length of their layout, see `get_v0_len`.
+pub fn get_account_version(data: &[u8], account_type: AccountType) -> Result<u8, ProgramError> {
+    if data.len() > DISCRIMINATOR_LEN && data[..DISCRIMINATOR_LEN] == account_type.discriminator() {
+        Ok(data[DISCRIMINATOR_LEN])
+    } else if get_v0_len(data, account_type) == Some(data.len()) {
+        Ok(0)
+    } else if data.len() >= 2
+        && data[0] == account_type as u8
+        && data[1] < DISCRIMINATOR_ACCOUNT_VERSION
+    {
+        Ok(data[1])
     } else {
-        Err(ContractError::DeserializeError.into())
+        Err(ProgramError::InvalidAccountData)
     }
 }
 
-pub fn get_game_info(data: &Ref<&mut [u8]>) -> Result<Game, ProgramError> {
-    if let Ok(data) = Game::try_from_slice(data) {
-        Ok(data)
-    } else {
-        Err(ContractError::DeserializeError.into())
+/// The length of the unversioned layout of `account_type`, if it had one.
+fn get_v0_len(data: &[u8], account_type: AccountType) -> Option<usize> {
+    match account_type {
+        AccountType::BettingInfo => Some(BETTING_INFO_V0_LEN),
+        AccountType::Game => Some(GAME_V0_LEN),
+        AccountType::User => user_v0_len(data),
+        AccountType::SupportedToken => Some(SUPPORTED_TOKEN_V0_LEN),
+        AccountType::TypePrice => Some(TYPE_PRICE_V0_LEN),
+        _ => None,
     }
 }
 
-pub fn get_user_info(data: &Ref<&mut [u8]>) -> Result<User, ProgramError> {
-    if let Ok(data) = User::try_from_slice(data) {
+/// The version new accounts of `account_type` are written with. Every type moved to the same
+/// one with the discriminator.
+pub fn get_latest_version(_account_type: AccountType) -> u8 {
+    DISCRIMINATOR_ACCOUNT_VERSION
+}
+
+fn deserialize<T: BorshDeserialize>(data: &[u8]) -> Result<T, ProgramError> {
+    if let Ok(data) = T::try_from_slice(data) {
         Ok(data)
     } else {
         Err(ContractError::DeserializeError.into())
     }
 }
 
-pub fn get_supported_token_info(data: &Ref<&mut [u8]>) -> Result<SupportedToken, ProgramError> {
-    if let Ok(data) = SupportedToken::try_from_slice(data) {
-        Ok(data)
-    } else {
-        Err(ContractError::DeserializeError.into())
+/// Reads an account of the current borsh layout that still starts with the one-byte tag of
+/// `account_type`. The discriminator only replaced the tag, the rest of the layout is the same.
+fn deserialize_tagged<T: BorshDeserialize>(
+    data: &[u8],
+    account_type: AccountType,
+) -> Result<T, ProgramError> {
+    deserialize(&[&account_type.discriminator()[..], &data[1..]].concat())
+}
+
+/// Reads a `Pod` layout straight from its fixed offsets. The account has to be exactly as long
+/// as the layout.
+fn read_pod<T: Pod>(data: &[u8]) -> Result<T, ProgramError> {
+    if data.len() != size_of::<T>() {
+        return Err(ContractError::DeserializeError.into());
     }
+
+    Ok(bytemuck::pod_read_unaligned(data))
 }
 
-pub fn get_type_price_info(data: &Ref<&mut [u8]>) -> Result<TypePrice, ProgramError> {
-    if let Ok(data) = TypePrice::try_from_slice(data) {
-        Ok(data)
-    } else {
-        Err(ContractError::DeserializeError.into())
+/// Reads an account of the current `Pod` layout that still starts with the one-byte tag of
+/// `account_type`, in the 8-byte header it had before the discriminator. The header now takes
+/// 16 bytes: the discriminator, then the 7 bytes that followed the tag, zero-padded.
+fn read_tagged_pod<T: Pod>(data: &[u8], account_type: AccountType) -> Result<T, ProgramError> {
+    const HEADER_LEN: usize = 2 * DISCRIMINATOR_LEN;
+
+    if data.len() + DISCRIMINATOR_LEN != size_of::<T>() {
+        return Err(ContractError::DeserializeError.into());
+    }
+
+    let mut bytes = vec![0; size_of::<T>()];
+    bytes[..DISCRIMINATOR_LEN].copy_from_slice(&account_type.discriminator());
+    bytes[DISCRIMINATOR_LEN..HEADER_LEN - 1].copy_from_slice(&data[1..DISCRIMINATOR_LEN]);
+    bytes[HEADER_LEN..].copy_from_slice(&data[DISCRIMINATOR_LEN..]);
+
+    read_pod(&bytes)
+}
+
+/// Writes a `Pod` layout over the start of `data`. The processors change their accounts through
+/// `PodAccount` instead; this is for building accounts off-chain.
+fn write_pod<T: Pod>(pod: &T, data: &mut [u8]) -> ProgramResult {
+    data.get_mut(..size_of::<T>())
+        .ok_or(ProgramError::AccountDataTooSmall)?
+        .copy_from_slice(bytes_of(pod));
+
+    Ok(())
+}
+
+pub fn get_betting_info(data: &[u8]) -> Result<BettingInfo, ProgramError> {
+    match get_account_version(data, AccountType::BettingInfo)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => {
+            read_pod::<PodBettingInfo>(data).map(|pod| BettingInfo::from(&pod))
+        }
+        POD_ACCOUNT_VERSION => read_tagged_pod::<PodBettingInfo>(data, AccountType::BettingInfo)
+            .map(|pod| BettingInfo::from(&pod)),
+        ACCOUNT_VERSION => deserialize::<BettingInfoV1>(data).map(BettingInfo::from),
+        0 => deserialize::<BettingInfoV0>(data).map(BettingInfo::from),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn pack_betting_info(betting_info: &BettingInfo, data: &mut [u8]) -> ProgramResult {
+    write_pod(&PodBettingInfo::from(betting_info), data)
+}
+
+pub fn get_game_info(data: &[u8]) -> Result<Game, ProgramError> {
+    match get_account_version(data, AccountType::Game)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => read_pod::<PodGame>(data).map(|pod| Game::from(&pod)),
+        POD_ACCOUNT_VERSION => {
+            read_tagged_pod::<PodGame>(data, AccountType::Game).map(|pod| Game::from(&pod))
+        }
+        ACCOUNT_VERSION => deserialize::<GameV1>(data).map(Game::from),
+        0 => deserialize::<GameV0>(data).map(Game::from),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn pack_game_info(game: &Game, data: &mut [u8]) -> ProgramResult {
+    write_pod(&PodGame::from(game), data)
+}
+
+pub fn get_user_info(data: &[u8]) -> Result<User, ProgramError> {
+    match get_account_version(data, AccountType::User)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => read_pod::<PodUser>(data).map(|pod| User::from(&pod)),
+        USER_ACCOUNT_VERSION => {
+            read_tagged_pod::<PodUser>(data, AccountType::User).map(|pod| User::from(&pod))
+        }
+        STATS_ACCOUNT_VERSION => read_pod::<UserV4>(data).map(|pod| User::from(&pod)),
+        PROFILE_ACCOUNT_VERSION => read_pod::<UserV3>(data).map(|pod| User::from(&pod)),
+        POD_ACCOUNT_VERSION => read_pod::<UserV2>(data).map(|pod| User::from(&pod)),
+        ACCOUNT_VERSION => deserialize::<UserV1>(data).map(User::from),
+        0 => deserialize::<UserV0>(data).map(User::from),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn pack_user_info(user: &User, data: &mut [u8]) -> ProgramResult {
+    write_pod(&PodUser::from(user), data)
+}
+
+pub fn get_supported_token_info(data: &[u8]) -> Result<SupportedToken, ProgramError> {
+    match get_account_version(data, AccountType::SupportedToken)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::SupportedToken),
+        0 => deserialize::<SupportedTokenV0>(data).map(SupportedToken::from),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_type_price_info(data: &[u8]) -> Result<TypePrice, ProgramError> {
+    match get_account_version(data, AccountType::TypePrice)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::TypePrice),
+        0 => deserialize::<TypePriceV0>(data).map(TypePrice::from),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_token_stats_info(data: &[u8]) -> Result<TokenStats, ProgramError> {
+    match get_account_version(data, AccountType::TokenStats)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::TokenStats),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_referral_code_info(data: &[u8]) -> Result<ReferralCode, ProgramError> {
+    match get_account_version(data, AccountType::ReferralCode)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::ReferralCode),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_referral_reward_info(data: &[u8]) -> Result<ReferralReward, ProgramError> {
+    match get_account_version(data, AccountType::ReferralReward)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::ReferralReward),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_role_info(data: &[u8]) -> Result<Role, ProgramError> {
+    match get_account_version(data, AccountType::Role)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::Role),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_pending_config_info(data: &[u8]) -> Result<PendingConfig, ProgramError> {
+    match get_account_version(data, AccountType::PendingConfig)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::PendingConfig),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_bot_info(data: &[u8]) -> Result<BotInfo, ProgramError> {
+    match get_account_version(data, AccountType::BotInfo)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::BotInfo),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_bot_registry_info(data: &[u8]) -> Result<BotRegistry, ProgramError> {
+    match get_account_version(data, AccountType::BotRegistry)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::BotRegistry),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+pub fn get_session_info(data: &[u8]) -> Result<Session, ProgramError> {
+    match get_account_version(data, AccountType::Session)? {
+        DISCRIMINATOR_ACCOUNT_VERSION => deserialize(data),
+        ACCOUNT_VERSION => deserialize_tagged(data, AccountType::Session),
+        _ => Err(ProgramError::InvalidAccountData),
     }
 }
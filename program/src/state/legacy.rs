@@ -0,0 +1,525 @@
+use crate::consts::{
+    ACCOUNT_VERSION, MAX_AVATAR_URI_LEN, MAX_NICKNAME_LEN, MAX_PASSWORD_LEN, MAX_REFERRAL_DEPTH,
+    PAUSE_JOIN_GAME, PAUSE_NEW_GAME, RESERVED_SPACE, SECONDS_IN_THE_DAY,
+};
+use crate::state::pod::read_padded;
+use crate::state::structs::{
+    AccountType, BettingInfo, Game, Limits, SupportedToken, TypePrice, User,
+};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// The admin that was hard-coded before it moved into `BettingInfo`.
+pub const LEGACY_ADMIN: &str = "4kMtMnYWFbsMc7M3jcdnfCceHaiXmrqaMz2QZQAmn88i";
+
+/// The borsh lengths of the layouts written before versioning, which is how these accounts
+/// are told apart from the versioned ones.
+pub const GAME_V0_LEN: usize = 32 * 4 + 8 * 3 + 1 + 8;
+
+pub const BETTING_INFO_V0_LEN: usize = 8 * 4 + 1 + 8 + 32;
+
+pub const SUPPORTED_TOKEN_V0_LEN: usize = 32 * 2 + 1;
+
+pub const TYPE_PRICE_V0_LEN: usize = 8;
+
+/// `UserV0` ends with the password, so its length is read from the string's length prefix.
+pub fn user_v0_len(data: &[u8]) -> Option<usize> {
+    const PASSWORD_OFFSET: usize = 32 * 2 + 3 + 8;
+
+    data.get(PASSWORD_OFFSET..PASSWORD_OFFSET + 4).map(|len| {
+        PASSWORD_OFFSET + 4 + u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct GameV0 {
+    pub gamer1: Pubkey,
+    pub gamer2: Pubkey,
+    pub token1: Pubkey,
+    pub token2: Pubkey,
+    pub amount1: u64,
+    pub amount2: u64,
+    pub latest_bet: u64,
+    pub closed: bool,
+    pub type_price: u64,
+}
+
+/// The manager does not carry over; it has to be granted its roles again.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BettingInfoV0 {
+    pub referrer_fee: u64,
+    pub admin_fee: u64,
+    pub global_fee: u64,
+    pub transaction_fee: u64,
+    pub accept_bets: bool,
+    pub close_delay: u64,
+    pub manager: Pubkey,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct SupportedTokenV0 {
+    pub mint: Pubkey,
+    pub feed: Pubkey,
+    pub is_stablecoin: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct UserV0 {
+    pub address: Pubkey,
+    pub referrer: Pubkey,
+    pub in_game: bool,
+    pub support_bots: bool,
+    pub is_bot: bool,
+    pub turnover: u64,
+    pub password: String,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct TypePriceV0 {
+    pub price: u64,
+}
+
+/// The borsh layouts used before `Game`, `BettingInfo` and `User` moved to `state::pod`.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct GameV1 {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub gamer1: Pubkey,
+    pub gamer2: Pubkey,
+    pub token1: Pubkey,
+    pub token2: Pubkey,
+    pub amount1: u64,
+    pub amount2: u64,
+    pub latest_bet: u64,
+    pub closed: bool,
+    pub type_price: u64,
+    pub winner: u8,
+    pub admin_fee: u64,
+    pub referrer_fee: u64,
+    pub distributed: u8,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 19],
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BettingInfoV1 {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub referrer_fee: u64,
+    pub admin_fee: u64,
+    pub global_fee: u64,
+    pub transaction_fee: u64,
+    pub pause_flags: u8,
+    pub close_delay: u64,
+    pub referral_tiers: [u64; MAX_REFERRAL_DEPTH],
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub config_delay: u64,
+    pub shutdown: bool,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 2],
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct UserV1 {
+    pub account_type: AccountType,
+    pub version: u8,
+    pub address: Pubkey,
+    pub referrer: Pubkey,
+    pub in_game: bool,
+    pub support_bots: bool,
+    pub is_bot: bool,
+    pub turnover: u64,
+    pub active_games: u64,
+    pub banned: bool,
+    pub ban_reason: u8,
+    pub ban_expiry: u64,
+    pub password: String,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+/// The `PodUser` layout of version 2, with the password still stored in plain text.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UserV2 {
+    pub account_type: u8,
+    pub version: u8,
+    pub bump: u8,
+    pub in_game: u8,
+    pub support_bots: u8,
+    pub is_bot: u8,
+    pub banned: u8,
+    pub ban_reason: u8,
+    pub turnover: u64,
+    pub active_games: u64,
+    pub ban_expiry: u64,
+    pub address: Pubkey,
+    pub referrer: Pubkey,
+    pub password: [u8; MAX_PASSWORD_LEN],
+    pub reserved: [u8; RESERVED_SPACE],
+}
+
+/// The `PodUser` layout of version 3, before the game statistics.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UserV3 {
+    pub account_type: u8,
+    pub version: u8,
+    pub bump: u8,
+    pub in_game: u8,
+    pub support_bots: u8,
+    pub is_bot: u8,
+    pub banned: u8,
+    pub ban_reason: u8,
+    pub turnover: u64,
+    pub active_games: u64,
+    pub ban_expiry: u64,
+    pub address: Pubkey,
+    pub referrer: Pubkey,
+    pub credential_hash: [u8; 32],
+    pub nickname: [u8; MAX_NICKNAME_LEN],
+    pub avatar_uri: [u8; MAX_AVATAR_URI_LEN],
+    pub referrer_changed: u8,
+    pub padding: [u8; 7],
+    pub reserved: [u8; RESERVED_SPACE - 8],
+}
+
+/// The `PodUser` layout of version 4, before the responsible-gaming limits.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct UserV4 {
+    pub account_type: u8,
+    pub version: u8,
+    pub bump: u8,
+    pub in_game: u8,
+    pub support_bots: u8,
+    pub is_bot: u8,
+    pub banned: u8,
+    pub ban_reason: u8,
+    pub turnover: u64,
+    pub active_games: u64,
+    pub ban_expiry: u64,
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub draws: u64,
+    pub cancellations: u64,
+    pub net_profit: i64,
+    pub last_game_at: u64,
+    pub address: Pubkey,
+    pub referrer: Pubkey,
+    pub credential_hash: [u8; 32],
+    pub nickname: [u8; MAX_NICKNAME_LEN],
+    pub avatar_uri: [u8; MAX_AVATAR_URI_LEN],
+    pub referrer_changed: u8,
+    pub padding: [u8; 7],
+    pub reserved: [u8; RESERVED_SPACE - 8],
+}
+
+impl From<GameV0> for Game {
+    fn from(game: GameV0) -> Self {
+        Game {
+            account_type: AccountType::Game,
+            version: ACCOUNT_VERSION,
+            gamer1: game.gamer1,
+            gamer2: game.gamer2,
+            token1: game.token1,
+            token2: game.token2,
+            amount1: game.amount1,
+            amount2: game.amount2,
+            latest_bet: game.latest_bet,
+            closed: game.closed,
+            type_price: game.type_price,
+            winner: 0,
+            admin_fee: 0,
+            referrer_fee: 0,
+            distributed: 0,
+            bump: 0,
+        }
+    }
+}
+
+impl From<BettingInfoV0> for BettingInfo {
+    fn from(betting_info: BettingInfoV0) -> Self {
+        BettingInfo {
+            account_type: AccountType::BettingInfo,
+            version: ACCOUNT_VERSION,
+            referrer_fee: betting_info.referrer_fee,
+            admin_fee: betting_info.admin_fee,
+            global_fee: betting_info.global_fee,
+            transaction_fee: betting_info.transaction_fee,
+            pause_flags: if betting_info.accept_bets {
+                0
+            } else {
+                PAUSE_NEW_GAME | PAUSE_JOIN_GAME
+            },
+            close_delay: betting_info.close_delay,
+            referral_tiers: [100, 0, 0],
+            admin: Pubkey::from_str(LEGACY_ADMIN).unwrap(),
+            pending_admin: Pubkey::default(),
+            config_delay: SECONDS_IN_THE_DAY,
+            shutdown: false,
+            bump: 0,
+        }
+    }
+}
+
+impl From<SupportedTokenV0> for SupportedToken {
+    fn from(supported_token: SupportedTokenV0) -> Self {
+        SupportedToken {
+            discriminator: AccountType::SupportedToken.discriminator(),
+            version: ACCOUNT_VERSION,
+            mint: supported_token.mint,
+            feed: supported_token.feed,
+            is_stablecoin: supported_token.is_stablecoin,
+            paused: false,
+            bump: 0,
+            reserved: [0; RESERVED_SPACE - 1],
+        }
+    }
+}
+
+impl From<UserV0> for User {
+    fn from(user: UserV0) -> Self {
+        User {
+            account_type: AccountType::User,
+            version: ACCOUNT_VERSION,
+            address: user.address,
+            referrer: user.referrer,
+            in_game: user.in_game,
+            support_bots: user.support_bots,
+            is_bot: user.is_bot,
+            turnover: user.turnover,
+            active_games: if user.in_game { 1 } else { 0 },
+            banned: false,
+            ban_reason: 0,
+            ban_expiry: 0,
+            games_played: 0,
+            wins: 0,
+            losses: 0,
+            draws: 0,
+            cancellations: 0,
+            net_profit: 0,
+            last_game_at: 0,
+            limits: Limits::default(),
+            pending_limits: Limits::default(),
+            pending_limits_at: 0,
+            limits_day: 0,
+            daily_games: 0,
+            daily_loss: 0,
+            credential_hash: None,
+            nickname: String::new(),
+            avatar_uri: String::new(),
+            referrer_changed: false,
+            bump: 0,
+        }
+    }
+}
+
+impl From<TypePriceV0> for TypePrice {
+    fn from(type_price: TypePriceV0) -> Self {
+        TypePrice {
+            discriminator: AccountType::TypePrice.discriminator(),
+            version: ACCOUNT_VERSION,
+            price: type_price.price,
+            paused: false,
+            bump: 0,
+            reserved: [0; RESERVED_SPACE - 1],
+        }
+    }
+}
+
+impl From<GameV1> for Game {
+    fn from(game: GameV1) -> Self {
+        Game {
+            account_type: AccountType::Game,
+            version: game.version,
+            gamer1: game.gamer1,
+            gamer2: game.gamer2,
+            token1: game.token1,
+            token2: game.token2,
+            amount1: game.amount1,
+            amount2: game.amount2,
+            latest_bet: game.latest_bet,
+            closed: game.closed,
+            type_price: game.type_price,
+            winner: game.winner,
+            admin_fee: game.admin_fee,
+            referrer_fee: game.referrer_fee,
+            distributed: game.distributed,
+            bump: game.bump,
+        }
+    }
+}
+
+impl From<BettingInfoV1> for BettingInfo {
+    fn from(betting_info: BettingInfoV1) -> Self {
+        BettingInfo {
+            account_type: AccountType::BettingInfo,
+            version: betting_info.version,
+            referrer_fee: betting_info.referrer_fee,
+            admin_fee: betting_info.admin_fee,
+            global_fee: betting_info.global_fee,
+            transaction_fee: betting_info.transaction_fee,
+            pause_flags: betting_info.pause_flags,
+            close_delay: betting_info.close_delay,
+            referral_tiers: betting_info.referral_tiers,
+            admin: betting_info.admin,
+            pending_admin: betting_info.pending_admin,
+            config_delay: betting_info.config_delay,
+            shutdown: betting_info.shutdown,
+            bump: betting_info.bump,
+        }
+    }
+}
+
+impl From<UserV1> for User {
+    fn from(user: UserV1) -> Self {
+        User {
+            account_type: AccountType::User,
+            version: user.version,
+            address: user.address,
+            referrer: user.referrer,
+            in_game: user.in_game,
+            support_bots: user.support_bots,
+            is_bot: user.is_bot,
+            turnover: user.turnover,
+            active_games: user.active_games,
+            banned: user.banned,
+            ban_reason: user.ban_reason,
+            ban_expiry: user.ban_expiry,
+            games_played: 0,
+            wins: 0,
+            losses: 0,
+            draws: 0,
+            cancellations: 0,
+            net_profit: 0,
+            last_game_at: 0,
+            limits: Limits::default(),
+            pending_limits: Limits::default(),
+            pending_limits_at: 0,
+            limits_day: 0,
+            daily_games: 0,
+            daily_loss: 0,
+            credential_hash: None,
+            nickname: String::new(),
+            avatar_uri: String::new(),
+            referrer_changed: false,
+            bump: user.bump,
+        }
+    }
+}
+
+impl From<&UserV4> for User {
+    fn from(user: &UserV4) -> Self {
+        User {
+            account_type: AccountType::User,
+            version: user.version,
+            address: user.address,
+            referrer: user.referrer,
+            in_game: user.in_game != 0,
+            support_bots: user.support_bots != 0,
+            is_bot: user.is_bot != 0,
+            turnover: user.turnover,
+            active_games: user.active_games,
+            banned: user.banned != 0,
+            ban_reason: user.ban_reason,
+            ban_expiry: user.ban_expiry,
+            games_played: user.games_played,
+            wins: user.wins,
+            losses: user.losses,
+            draws: user.draws,
+            cancellations: user.cancellations,
+            net_profit: user.net_profit,
+            last_game_at: user.last_game_at,
+            limits: Limits::default(),
+            pending_limits: Limits::default(),
+            pending_limits_at: 0,
+            limits_day: 0,
+            daily_games: 0,
+            daily_loss: 0,
+            credential_hash: Some(user.credential_hash).filter(|hash| *hash != [0; 32]),
+            nickname: read_padded(&user.nickname),
+            avatar_uri: read_padded(&user.avatar_uri),
+            referrer_changed: user.referrer_changed != 0,
+            bump: user.bump,
+        }
+    }
+}
+
+impl From<&UserV3> for User {
+    fn from(user: &UserV3) -> Self {
+        User {
+            account_type: AccountType::User,
+            version: user.version,
+            address: user.address,
+            referrer: user.referrer,
+            in_game: user.in_game != 0,
+            support_bots: user.support_bots != 0,
+            is_bot: user.is_bot != 0,
+            turnover: user.turnover,
+            active_games: user.active_games,
+            banned: user.banned != 0,
+            ban_reason: user.ban_reason,
+            ban_expiry: user.ban_expiry,
+            games_played: 0,
+            wins: 0,
+            losses: 0,
+            draws: 0,
+            cancellations: 0,
+            net_profit: 0,
+            last_game_at: 0,
+            limits: Limits::default(),
+            pending_limits: Limits::default(),
+            pending_limits_at: 0,
+            limits_day: 0,
+            daily_games: 0,
+            daily_loss: 0,
+            credential_hash: Some(user.credential_hash).filter(|hash| *hash != [0; 32]),
+            nickname: read_padded(&user.nickname),
+            avatar_uri: read_padded(&user.avatar_uri),
+            referrer_changed: user.referrer_changed != 0,
+            bump: user.bump,
+        }
+    }
+}
+
+impl From<&UserV2> for User {
+    fn from(user: &UserV2) -> Self {
+        User {
+            account_type: AccountType::User,
+            version: user.version,
+            address: user.address,
+            referrer: user.referrer,
+            in_game: user.in_game != 0,
+            support_bots: user.support_bots != 0,
+            is_bot: user.is_bot != 0,
+            turnover: user.turnover,
+            active_games: user.active_games,
+            banned: user.banned != 0,
+            ban_reason: user.ban_reason,
+            ban_expiry: user.ban_expiry,
+            games_played: 0,
+            wins: 0,
+            losses: 0,
+            draws: 0,
+            cancellations: 0,
+            net_profit: 0,
+            last_game_at: 0,
+            limits: Limits::default(),
+            pending_limits: Limits::default(),
+            pending_limits_at: 0,
+            limits_day: 0,
+            daily_games: 0,
+            daily_loss: 0,
+            credential_hash: None,
+            nickname: String::new(),
+            avatar_uri: String::new(),
+            referrer_changed: false,
+            bump: user.bump,
+        }
+    }
+}
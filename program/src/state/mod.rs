@@ -1,2 +1,5 @@
+pub mod accounts;
 pub mod helpers;
+pub mod legacy;
+pub mod pod;
 pub mod structs;
@@ -0,0 +1,296 @@
+use crate::consts::{
+    DISCRIMINATOR_ACCOUNT_VERSION, MAX_AVATAR_URI_LEN, MAX_NICKNAME_LEN, MAX_REFERRAL_DEPTH,
+    RESERVED_SPACE,
+};
+use crate::state::structs::{AccountType, BettingInfo, Game, Limits, User};
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+/// A `bool` in a `Pod` layout; any byte but 0 is `true`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PodBool(u8);
+
+impl PodBool {
+    pub fn get(self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl From<bool> for PodBool {
+    fn from(value: bool) -> Self {
+        PodBool(value as u8)
+    }
+}
+
+/// On-chain layout of `Game`. Flags follow the header and fill it up to 16 bytes so the struct
+/// has no padding; it is read and written at fixed offsets without borsh.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct PodGame {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub closed: PodBool,
+    pub winner: u8,
+    pub distributed: u8,
+    pub padding: [u8; 3],
+    pub amount1: u64,
+    pub amount2: u64,
+    pub latest_bet: u64,
+    pub type_price: u64,
+    pub admin_fee: u64,
+    pub referrer_fee: u64,
+    pub gamer1: Pubkey,
+    pub gamer2: Pubkey,
+    pub token1: Pubkey,
+    pub token2: Pubkey,
+    pub reserved: [u8; RESERVED_SPACE],
+}
+
+/// On-chain layout of `User`. A zeroed `credential_hash` means none is set; nickname and
+/// avatar URI are zero-padded.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct PodUser {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub in_game: PodBool,
+    pub support_bots: PodBool,
+    pub is_bot: PodBool,
+    pub banned: PodBool,
+    pub ban_reason: u8,
+    pub header_padding: u8,
+    pub turnover: u64,
+    pub active_games: u64,
+    pub ban_expiry: u64,
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub draws: u64,
+    pub cancellations: u64,
+    pub net_profit: i64,
+    pub last_game_at: u64,
+    pub limits: Limits,
+    pub pending_limits: Limits,
+    pub pending_limits_at: u64,
+    pub limits_day: u64,
+    pub daily_games: u64,
+    pub daily_loss: u64,
+    pub address: Pubkey,
+    pub referrer: Pubkey,
+    pub credential_hash: [u8; 32],
+    pub nickname: [u8; MAX_NICKNAME_LEN],
+    pub avatar_uri: [u8; MAX_AVATAR_URI_LEN],
+    pub referrer_changed: PodBool,
+    pub padding: [u8; 7],
+    pub reserved: [u8; RESERVED_SPACE - 8],
+}
+
+/// On-chain layout of `BettingInfo`.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct PodBettingInfo {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bump: u8,
+    pub pause_flags: u8,
+    pub shutdown: PodBool,
+    pub padding: [u8; 4],
+    pub referrer_fee: u64,
+    pub admin_fee: u64,
+    pub global_fee: u64,
+    pub transaction_fee: u64,
+    pub close_delay: u64,
+    pub referral_tiers: [u64; MAX_REFERRAL_DEPTH],
+    pub config_delay: u64,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub reserved: [u8; RESERVED_SPACE],
+}
+
+impl From<&PodGame> for Game {
+    fn from(game: &PodGame) -> Self {
+        Game {
+            account_type: AccountType::Game,
+            version: game.version,
+            gamer1: game.gamer1,
+            gamer2: game.gamer2,
+            token1: game.token1,
+            token2: game.token2,
+            amount1: game.amount1,
+            amount2: game.amount2,
+            latest_bet: game.latest_bet,
+            closed: game.closed.get(),
+            type_price: game.type_price,
+            winner: game.winner,
+            admin_fee: game.admin_fee,
+            referrer_fee: game.referrer_fee,
+            distributed: game.distributed,
+            bump: game.bump,
+        }
+    }
+}
+
+impl From<&Game> for PodGame {
+    fn from(game: &Game) -> Self {
+        PodGame {
+            discriminator: AccountType::Game.discriminator(),
+            version: DISCRIMINATOR_ACCOUNT_VERSION,
+            bump: game.bump,
+            closed: game.closed.into(),
+            winner: game.winner,
+            distributed: game.distributed,
+            amount1: game.amount1,
+            amount2: game.amount2,
+            latest_bet: game.latest_bet,
+            type_price: game.type_price,
+            admin_fee: game.admin_fee,
+            referrer_fee: game.referrer_fee,
+            gamer1: game.gamer1,
+            gamer2: game.gamer2,
+            token1: game.token1,
+            token2: game.token2,
+            ..PodGame::zeroed()
+        }
+    }
+}
+
+impl From<&PodUser> for User {
+    fn from(user: &PodUser) -> Self {
+        User {
+            account_type: AccountType::User,
+            version: user.version,
+            address: user.address,
+            referrer: user.referrer,
+            in_game: user.in_game.get(),
+            support_bots: user.support_bots.get(),
+            is_bot: user.is_bot.get(),
+            turnover: user.turnover,
+            active_games: user.active_games,
+            banned: user.banned.get(),
+            ban_reason: user.ban_reason,
+            ban_expiry: user.ban_expiry,
+            games_played: user.games_played,
+            wins: user.wins,
+            losses: user.losses,
+            draws: user.draws,
+            cancellations: user.cancellations,
+            net_profit: user.net_profit,
+            last_game_at: user.last_game_at,
+            limits: user.limits,
+            pending_limits: user.pending_limits,
+            pending_limits_at: user.pending_limits_at,
+            limits_day: user.limits_day,
+            daily_games: user.daily_games,
+            daily_loss: user.daily_loss,
+            credential_hash: Some(user.credential_hash).filter(|hash| *hash != [0; 32]),
+            nickname: read_padded(&user.nickname),
+            avatar_uri: read_padded(&user.avatar_uri),
+            referrer_changed: user.referrer_changed.get(),
+            bump: user.bump,
+        }
+    }
+}
+
+impl From<&User> for PodUser {
+    fn from(user: &User) -> Self {
+        let mut pod = PodUser {
+            discriminator: AccountType::User.discriminator(),
+            version: DISCRIMINATOR_ACCOUNT_VERSION,
+            bump: user.bump,
+            in_game: user.in_game.into(),
+            support_bots: user.support_bots.into(),
+            is_bot: user.is_bot.into(),
+            banned: user.banned.into(),
+            ban_reason: user.ban_reason,
+            turnover: user.turnover,
+            active_games: user.active_games,
+            ban_expiry: user.ban_expiry,
+            games_played: user.games_played,
+            wins: user.wins,
+            losses: user.losses,
+            draws: user.draws,
+            cancellations: user.cancellations,
+            net_profit: user.net_profit,
+            last_game_at: user.last_game_at,
+            limits: user.limits,
+            pending_limits: user.pending_limits,
+            pending_limits_at: user.pending_limits_at,
+            limits_day: user.limits_day,
+            daily_games: user.daily_games,
+            daily_loss: user.daily_loss,
+            address: user.address,
+            referrer: user.referrer,
+            credential_hash: user.credential_hash.unwrap_or_default(),
+            referrer_changed: user.referrer_changed.into(),
+            ..PodUser::zeroed()
+        };
+        write_padded(&user.nickname, &mut pod.nickname);
+        write_padded(&user.avatar_uri, &mut pod.avatar_uri);
+
+        pod
+    }
+}
+
+impl From<&PodBettingInfo> for BettingInfo {
+    fn from(betting_info: &PodBettingInfo) -> Self {
+        BettingInfo {
+            account_type: AccountType::BettingInfo,
+            version: betting_info.version,
+            referrer_fee: betting_info.referrer_fee,
+            admin_fee: betting_info.admin_fee,
+            global_fee: betting_info.global_fee,
+            transaction_fee: betting_info.transaction_fee,
+            pause_flags: betting_info.pause_flags,
+            close_delay: betting_info.close_delay,
+            referral_tiers: betting_info.referral_tiers,
+            admin: betting_info.admin,
+            pending_admin: betting_info.pending_admin,
+            config_delay: betting_info.config_delay,
+            shutdown: betting_info.shutdown.get(),
+            bump: betting_info.bump,
+        }
+    }
+}
+
+impl From<&BettingInfo> for PodBettingInfo {
+    fn from(betting_info: &BettingInfo) -> Self {
+        PodBettingInfo {
+            discriminator: AccountType::BettingInfo.discriminator(),
+            version: DISCRIMINATOR_ACCOUNT_VERSION,
+            bump: betting_info.bump,
+            pause_flags: betting_info.pause_flags,
+            shutdown: betting_info.shutdown.into(),
+            referrer_fee: betting_info.referrer_fee,
+            admin_fee: betting_info.admin_fee,
+            global_fee: betting_info.global_fee,
+            transaction_fee: betting_info.transaction_fee,
+            close_delay: betting_info.close_delay,
+            referral_tiers: betting_info.referral_tiers,
+            config_delay: betting_info.config_delay,
+            admin: betting_info.admin,
+            pending_admin: betting_info.pending_admin,
+            ..PodBettingInfo::zeroed()
+        }
+    }
+}
+
+/// A zero-padded string; it ends at the first zero byte.
+pub fn read_padded(bytes: &[u8]) -> String {
+    let len = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(bytes.len());
+
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+/// Copies `value` into `bytes`, cutting it at the end of the field. The rest of the field is
+/// zeroed, so a shorter value replaces a longer one.
+pub fn write_padded(value: &str, bytes: &mut [u8]) {
+    let len = value.len().min(bytes.len());
+    bytes[..len].copy_from_slice(&value.as_bytes()[..len]);
+    bytes[len..].iter_mut().for_each(|byte| *byte = 0);
+}
@@ -1,8 +1,56 @@
+use crate::consts::{MAX_REFERRAL_DEPTH, RESERVED_SPACE};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
 use solana_program::pubkey::Pubkey;
 
-#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+/// The kind of an account. Accounts lead with its `discriminator`, followed by their version,
+/// so one account can't be read as another; accounts written before version
+/// `DISCRIMINATOR_ACCOUNT_VERSION` lead with the one-byte tag instead.
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum AccountType {
+    Uninitialized,
+    BettingInfo,
+    Game,
+    User,
+    SupportedToken,
+    TypePrice,
+    TokenStats,
+    ReferralCode,
+    ReferralReward,
+    Role,
+    PendingConfig,
+    BotInfo,
+    BotRegistry,
+    Session,
+}
+
+impl AccountType {
+    /// The first 8 bytes of `sha256("account:<type name>")`.
+    pub const fn discriminator(self) -> [u8; 8] {
+        match self {
+            AccountType::Uninitialized => [0; 8],
+            AccountType::BettingInfo => [252, 39, 183, 38, 1, 223, 166, 14],
+            AccountType::Game => [27, 90, 166, 125, 74, 100, 121, 18],
+            AccountType::User => [159, 117, 95, 227, 239, 151, 58, 236],
+            AccountType::SupportedToken => [56, 162, 96, 99, 193, 245, 204, 108],
+            AccountType::TypePrice => [237, 230, 95, 157, 93, 55, 202, 196],
+            AccountType::TokenStats => [7, 126, 25, 232, 73, 79, 202, 236],
+            AccountType::ReferralCode => [227, 239, 247, 224, 128, 187, 44, 229],
+            AccountType::ReferralReward => [208, 26, 65, 47, 113, 179, 92, 114],
+            AccountType::Role => [46, 219, 197, 24, 233, 249, 253, 154],
+            AccountType::PendingConfig => [109, 48, 178, 191, 125, 67, 26, 70],
+            AccountType::BotInfo => [214, 235, 174, 90, 220, 253, 158, 79],
+            AccountType::BotRegistry => [38, 218, 85, 55, 225, 233, 119, 55],
+            AccountType::Session => [243, 81, 72, 115, 214, 188, 72, 144],
+        }
+    }
+}
+
+/// Stored as `PodGame`, see `state::helpers`.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Game {
+    pub account_type: AccountType,
+    pub version: u8,
     pub gamer1: Pubkey,
     pub gamer2: Pubkey,
     pub token1: Pubkey,
@@ -12,38 +60,230 @@ pub struct Game {
     pub latest_bet: u64,
     pub closed: bool,
     pub type_price: u64,
+    /// `WINNER_GAMER1` or `WINNER_GAMER2` once settled, 0 otherwise.
+    pub winner: u8,
+    /// Fees taken from the first stake at settlement. The rest of it and the whole second
+    /// stake go to the winner.
+    pub admin_fee: u64,
+    pub referrer_fee: u64,
+    /// Bitmask of the `PAYOUT_*` transfers already made by `Distribute`.
+    pub distributed: u8,
+    pub bump: u8,
 }
 
-#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+/// Stored as `PodBettingInfo`, see `state::helpers`.
+#[derive(Clone, Debug, PartialEq)]
 pub struct BettingInfo {
+    pub account_type: AccountType,
+    pub version: u8,
     pub referrer_fee: u64,
     pub admin_fee: u64,
     pub global_fee: u64,
     pub transaction_fee: u64,
-    pub accept_bets: bool,
+    pub pause_flags: u8,
     pub close_delay: u64,
-    pub manager: Pubkey,
+    pub referral_tiers: [u64; MAX_REFERRAL_DEPTH],
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub config_delay: u64,
+    pub shutdown: bool,
+    pub bump: u8,
 }
 
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct SupportedToken {
+    pub discriminator: [u8; 8],
+    pub version: u8,
     pub mint: Pubkey,
     pub feed: Pubkey,
     pub is_stablecoin: bool,
+    pub paused: bool,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
 }
 
-#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+/// Stored as `PodUser`, see `state::helpers`.
+#[derive(Clone, Debug, PartialEq)]
 pub struct User {
+    pub account_type: AccountType,
+    pub version: u8,
     pub address: Pubkey,
     pub referrer: Pubkey,
     pub in_game: bool,
     pub support_bots: bool,
     pub is_bot: bool,
     pub turnover: u64,
-    pub password: String,
+    pub active_games: u64,
+    pub banned: bool,
+    pub ban_reason: u8,
+    pub ban_expiry: u64,
+    /// Games that had an opponent: settled ones and draws. Games closed before anyone joined
+    /// are only counted in `cancellations`.
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub draws: u64,
+    pub cancellations: u64,
+    /// In the units of the game type prices, after fees and penalties.
+    pub net_profit: i64,
+    pub last_game_at: u64,
+    pub limits: Limits,
+    /// Looser limits requested with `SetLimits`, applied from `pending_limits_at` on.
+    pub pending_limits: Limits,
+    pub pending_limits_at: u64,
+    /// Start of the 24-hour window of the daily counters, as a unix timestamp.
+    pub limits_day: u64,
+    pub daily_games: u64,
+    /// Stakes lost in games settled within the window.
+    pub daily_loss: u64,
+    /// Salted hash of the user's credentials; the password itself never goes on-chain.
+    pub credential_hash: Option<[u8; 32]>,
+    pub nickname: String,
+    pub avatar_uri: String,
+    /// Set by the first `ChangeReferrer`; later changes need a zero turnover.
+    pub referrer_changed: bool,
+    pub bump: u8,
 }
 
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct TypePrice {
+    pub discriminator: [u8; 8],
+    pub version: u8,
     pub price: u64,
+    pub paused: bool,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct TokenStats {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub mint: Pubkey,
+    pub staked_volume: u64,
+    pub games_settled: u64,
+    pub admin_fees: u64,
+    pub referrer_fees: u64,
+    pub penalties: u64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ReferralCode {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub code: String,
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ReferralReward {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub claimed: u64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct Role {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub address: Pubkey,
+    pub permissions: u8,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+/// Self-imposed limits of a user; 0 means no limit. Amounts are in the units of the game type
+/// prices.
+#[repr(C)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Pod,
+    Zeroable,
+)]
+pub struct Limits {
+    pub max_type_price: u64,
+    pub max_daily_games: u64,
+    pub daily_loss_limit: u64,
+    /// No games until this timestamp.
+    pub excluded_until: u64,
+}
+
+/// The timelocked part of `BettingInfo`.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct Config {
+    pub referrer_fee: u64,
+    pub admin_fee: u64,
+    pub global_fee: u64,
+    pub transaction_fee: u64,
+    pub close_delay: u64,
+    pub referral_tiers: [u64; MAX_REFERRAL_DEPTH],
+    pub config_delay: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct PendingConfig {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub config: Config,
+    pub execute_after: u64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+/// Limits of a bot; a limit of 0 means unlimited.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BotInfo {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bot: Pubkey,
+    pub suspended: bool,
+    pub max_games: u64,
+    pub max_type_price: u64,
+    pub daily_volume_limit: u64,
+    pub day: u64,
+    pub daily_volume: u64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BotRegistry {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub bots: Vec<Pubkey>,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
+}
+
+/// A key the user allowed to open and join games on their behalf, see `CreateSession`.
+/// `max_stake_per_game`, `max_total` and `spent` are in `TypePrice::price` units rather than
+/// token amounts; a limit of 0 means unlimited.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct Session {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub user: Pubkey,
+    pub delegate: Pubkey,
+    pub expires_at: u64,
+    pub max_stake_per_game: u64,
+    pub max_total: u64,
+    pub spent: u64,
+    pub bump: u8,
+    pub reserved: [u8; RESERVED_SPACE - 1],
 }
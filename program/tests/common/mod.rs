@@ -0,0 +1,421 @@
+//! Setup shared by the integration tests. Accounts are seeded in the layouts the program
+//! writes, so a test can start from any state without going through the oracle-priced bets.
+#![allow(dead_code)]
+
+use betting::consts::{
+    BETTING, DISCRIMINATOR_ACCOUNT_VERSION, GAME, REFERRAL, RESERVED_SPACE, STATS, TYPE_PRICE,
+    USER, WHITELIST,
+};
+use betting::error::ContractError;
+use betting::state::helpers::{
+    get_game_info, get_referral_reward_info, get_token_stats_info, get_user_info,
+    pack_betting_info, pack_game_info, pack_user_info, BETTING_INFO_LEN, GAME_LEN, USER_LEN,
+};
+use betting::state::structs::{
+    AccountType, BettingInfo, Game, Limits, ReferralReward, SupportedToken, TokenStats, TypePrice,
+    User,
+};
+use borsh::BorshSerialize;
+use solana_program::program_option::COption;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_program;
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use spl_associated_token_account::get_associated_token_address;
+
+pub const TYPE: u64 = 1;
+
+pub const TYPE_PRICE_VALUE: u64 = 15;
+
+pub fn program_test() -> ProgramTest {
+    ProgramTest::new(
+        "betting",
+        betting::id(),
+        processor!(betting::entrypoint::process_instruction),
+    )
+}
+
+/// The error `ContractError` `error` fails the first instruction of a transaction with.
+pub fn contract_error(error: ContractError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+pub async fn process(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), TransactionError> {
+    let mut transaction = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+    let mut keypairs = vec![payer];
+    keypairs.extend_from_slice(signers);
+    transaction.sign(&keypairs, recent_blockhash);
+
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|err| err.unwrap())
+}
+
+pub fn add_account(program_test: &mut ProgramTest, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+    program_test.add_account(
+        address,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()).max(1),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+/// A new keypair with enough SOL to pay rent for the accounts it opens.
+pub fn add_wallet(program_test: &mut ProgramTest) -> Keypair {
+    let wallet = Keypair::new();
+    fund(program_test, &wallet.pubkey());
+
+    wallet
+}
+
+pub fn fund(program_test: &mut ProgramTest, wallet: &Pubkey) {
+    program_test.add_account(
+        *wallet,
+        Account {
+            lamports: 1_000_000_000,
+            data: vec![],
+            owner: system_program::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+fn find_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &betting::id())
+}
+
+/// `BettingInfo` as `Init` creates it.
+pub fn betting_info(admin: Pubkey) -> BettingInfo {
+    BettingInfo {
+        account_type: AccountType::BettingInfo,
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        referrer_fee: 50,
+        admin_fee: 50,
+        global_fee: 10,
+        transaction_fee: 0,
+        pause_flags: 0,
+        close_delay: 300,
+        referral_tiers: [100, 0, 0],
+        admin,
+        pending_admin: Pubkey::default(),
+        config_delay: 0,
+        shutdown: false,
+        bump: find_address(&[BETTING]).1,
+    }
+}
+
+pub fn add_betting_info(program_test: &mut ProgramTest, betting_info: &BettingInfo) {
+    let mut data = vec![0; BETTING_INFO_LEN];
+    pack_betting_info(betting_info, &mut data).unwrap();
+
+    add_account(
+        program_test,
+        find_address(&[BETTING]).0,
+        betting::id(),
+        data,
+    );
+}
+
+/// `User` as `Registration` creates it.
+pub fn user(address: Pubkey, referrer: Pubkey) -> User {
+    User {
+        account_type: AccountType::User,
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        address,
+        referrer,
+        in_game: false,
+        support_bots: false,
+        is_bot: false,
+        turnover: 0,
+        active_games: 0,
+        banned: false,
+        ban_reason: 0,
+        ban_expiry: 0,
+        games_played: 0,
+        wins: 0,
+        losses: 0,
+        draws: 0,
+        cancellations: 0,
+        net_profit: 0,
+        last_game_at: 0,
+        limits: Limits::default(),
+        pending_limits: Limits::default(),
+        pending_limits_at: 0,
+        limits_day: 0,
+        daily_games: 0,
+        daily_loss: 0,
+        credential_hash: None,
+        nickname: String::new(),
+        avatar_uri: String::new(),
+        referrer_changed: false,
+        bump: find_address(&[USER, &address.to_bytes()]).1,
+    }
+}
+
+pub fn add_user(program_test: &mut ProgramTest, user: &User) {
+    let mut data = vec![0; USER_LEN];
+    pack_user_info(user, &mut data).unwrap();
+
+    add_account(
+        program_test,
+        user_address(&user.address),
+        betting::id(),
+        data,
+    );
+}
+
+pub fn user_address(wallet: &Pubkey) -> Pubkey {
+    find_address(&[USER, &wallet.to_bytes()]).0
+}
+
+/// A game of type `TYPE` that `gamer2` joined with the same stake in the same token.
+pub fn joined_game(gamer1: Pubkey, gamer2: Pubkey, mint: Pubkey, amount: u64) -> Game {
+    Game {
+        account_type: AccountType::Game,
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        gamer1,
+        gamer2,
+        token1: mint,
+        token2: mint,
+        amount1: amount,
+        amount2: amount,
+        latest_bet: 0,
+        closed: false,
+        type_price: TYPE_PRICE_VALUE,
+        winner: 0,
+        admin_fee: 0,
+        referrer_fee: 0,
+        distributed: 0,
+        bump: find_address(&[GAME, &gamer1.to_bytes()]).1,
+    }
+}
+
+/// Seeds `game` together with its token account holding both stakes.
+pub fn add_game(program_test: &mut ProgramTest, game: &Game) {
+    let mut data = vec![0; GAME_LEN];
+    pack_game_info(game, &mut data).unwrap();
+
+    let address = game_address(&game.gamer1);
+    add_account(program_test, address, betting::id(), data);
+
+    add_token_account(
+        program_test,
+        &address,
+        &game.token1,
+        game.amount1 + game.amount2,
+    );
+}
+
+pub fn game_address(gamer1: &Pubkey) -> Pubkey {
+    find_address(&[GAME, &gamer1.to_bytes()]).0
+}
+
+/// The type price `TYPE` and the whitelisted `mint` with its stats, as the first bet leaves them.
+pub fn add_market(program_test: &mut ProgramTest, mint: &Pubkey) {
+    let type_price = TypePrice {
+        discriminator: AccountType::TypePrice.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        price: TYPE_PRICE_VALUE,
+        paused: false,
+        bump: find_address(&[TYPE_PRICE, TYPE.to_string().as_bytes()]).1,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+    add_account(
+        program_test,
+        find_address(&[TYPE_PRICE, TYPE.to_string().as_bytes()]).0,
+        betting::id(),
+        type_price.try_to_vec().unwrap(),
+    );
+
+    let supported_token = SupportedToken {
+        discriminator: AccountType::SupportedToken.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        mint: *mint,
+        feed: feed_address(mint),
+        is_stablecoin: false,
+        paused: false,
+        bump: find_address(&[WHITELIST, &mint.to_bytes()]).1,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+    add_account(
+        program_test,
+        find_address(&[WHITELIST, &mint.to_bytes()]).0,
+        betting::id(),
+        supported_token.try_to_vec().unwrap(),
+    );
+
+    let stats = TokenStats {
+        discriminator: AccountType::TokenStats.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        mint: *mint,
+        staked_volume: 0,
+        games_settled: 0,
+        admin_fees: 0,
+        referrer_fees: 0,
+        penalties: 0,
+        bump: find_address(&[STATS, &mint.to_bytes()]).1,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+    add_account(
+        program_test,
+        stats_address(mint),
+        betting::id(),
+        stats.try_to_vec().unwrap(),
+    );
+
+    add_mint(program_test, mint);
+}
+
+/// The Chainlink feed whitelisted for `mint`.
+pub fn feed_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[&mint.to_bytes()], &chainlink_solana::id()).0
+}
+
+pub fn stats_address(mint: &Pubkey) -> Pubkey {
+    find_address(&[STATS, &mint.to_bytes()]).0
+}
+
+/// The reward PDA of `referrer` in `mint`, with `balance` waiting to be claimed.
+pub fn add_referral_reward(
+    program_test: &mut ProgramTest,
+    referrer: &Pubkey,
+    mint: &Pubkey,
+    balance: u64,
+) {
+    let (address, bump) = find_address(&[REFERRAL, &referrer.to_bytes(), &mint.to_bytes()]);
+
+    let reward = ReferralReward {
+        discriminator: AccountType::ReferralReward.discriminator(),
+        version: DISCRIMINATOR_ACCOUNT_VERSION,
+        referrer: *referrer,
+        mint: *mint,
+        balance,
+        claimed: 0,
+        bump,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+
+    add_account(
+        program_test,
+        address,
+        betting::id(),
+        reward.try_to_vec().unwrap(),
+    );
+}
+
+pub fn referral_reward_address(referrer: &Pubkey, mint: &Pubkey) -> Pubkey {
+    find_address(&[REFERRAL, &referrer.to_bytes(), &mint.to_bytes()]).0
+}
+
+pub fn add_mint(program_test: &mut ProgramTest, mint: &Pubkey) {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::None,
+        supply: u64::MAX / 2,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+
+    add_account(program_test, *mint, spl_token::id(), data);
+}
+
+/// The associated token account of `owner` holding `amount` of `mint`.
+pub fn add_token_account(
+    program_test: &mut ProgramTest,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+
+    add_account(
+        program_test,
+        get_associated_token_address(owner, mint),
+        spl_token::id(),
+        data,
+    );
+}
+
+pub async fn get_token_amount(
+    banks_client: &mut BanksClient,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> u64 {
+    match banks_client
+        .get_account(get_associated_token_address(owner, mint))
+        .await
+        .unwrap()
+    {
+        Some(account) => {
+            spl_token::state::Account::unpack(&account.data)
+                .unwrap()
+                .amount
+        }
+        None => 0,
+    }
+}
+
+pub async fn get_data(banks_client: &mut BanksClient, address: Pubkey) -> Option<Vec<u8>> {
+    banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .map(|account| account.data)
+}
+
+pub async fn get_user(banks_client: &mut BanksClient, wallet: &Pubkey) -> User {
+    let data = get_data(banks_client, user_address(wallet)).await.unwrap();
+    get_user_info(&data).unwrap()
+}
+
+pub async fn get_game(banks_client: &mut BanksClient, gamer1: &Pubkey) -> Game {
+    let data = get_data(banks_client, game_address(gamer1)).await.unwrap();
+    get_game_info(&data).unwrap()
+}
+
+pub async fn get_stats(banks_client: &mut BanksClient, mint: &Pubkey) -> TokenStats {
+    let data = get_data(banks_client, stats_address(mint)).await.unwrap();
+    get_token_stats_info(&data).unwrap()
+}
+
+pub async fn get_referral_reward(
+    banks_client: &mut BanksClient,
+    referrer: &Pubkey,
+    mint: &Pubkey,
+) -> ReferralReward {
+    let data = get_data(banks_client, referral_reward_address(referrer, mint))
+        .await
+        .unwrap();
+    get_referral_reward_info(&data).unwrap()
+}
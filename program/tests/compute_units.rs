@@ -0,0 +1,335 @@
+//! Compute units each instruction needs with the PDA bumps stored in the accounts, against a
+//! build with the `derive-bumps` feature, which derives every address with
+//! `find_program_address` as the program did before the bumps were stored.
+//!
+//! The native processor isn't metered, so the test only runs against the BPF builds:
+//!
+//! `make bench`
+//!
+//! builds `betting_derive_bumps.so` next to `betting.so` and runs it with `cargo test-bpf`.
+//! `Bet` and `JoinGame` read their price from a mocked Chainlink store.
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use betting::consts::{PAYOUT_WINNER, PRECISION, WINNER_GAMER1};
+use betting::instruction;
+use betting::state::structs::{Game, Limits};
+use borsh::BorshSerialize;
+use chainlink_solana::Round;
+use common::*;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{InvokeContext, ProgramTest};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+/// The program built with `derive-bumps`.
+const BEFORE: &str = "betting_derive_bumps";
+
+const STAKE: u64 = 1_000_000;
+
+const REWARD: u64 = 50_000;
+
+/// The price the mocked feed answers with, so a bet of `TYPE` stakes 1.5 * 10^7 base units.
+const PRICE: u64 = 100 * PRECISION;
+
+const MAX_UNITS: u64 = 200_000;
+
+#[derive(Clone, Copy, Debug)]
+enum Case {
+    Bet,
+    JoinGame,
+    SetLimits,
+    SetTypePrice,
+    LockBets,
+    BanUser,
+    ForcedClose,
+    Settle,
+    DistributeReferrers,
+    Distribute,
+    ClaimReferralRewards,
+    Deregister,
+}
+
+const CASES: [Case; 12] = [
+    Case::Bet,
+    Case::JoinGame,
+    Case::SetLimits,
+    Case::SetTypePrice,
+    Case::LockBets,
+    Case::BanUser,
+    Case::ForcedClose,
+    Case::Settle,
+    Case::DistributeReferrers,
+    Case::Distribute,
+    Case::ClaimReferralRewards,
+    Case::Deregister,
+];
+
+/// Answers every query to the Chainlink store with the latest round at `PRICE`.
+fn mock_chainlink(
+    _first_instruction_account: usize,
+    _data: &[u8],
+    invoke_context: &mut InvokeContext,
+) -> Result<(), InstructionError> {
+    let round = Round {
+        round_id: 1,
+        slot: 0,
+        timestamp: 0,
+        answer: PRICE as i128,
+    };
+
+    invoke_context.return_data = (chainlink_solana::id(), round.try_to_vec().unwrap());
+
+    Ok(())
+}
+
+/// The wallets every case runs with. A fresh bank is started for every run, since the compute
+/// budget is fixed when it starts. The referrer also bets and joins.
+struct Bench {
+    admin: Keypair,
+    gamer1: Pubkey,
+    gamer2: Pubkey,
+    referrer: Keypair,
+    mint: Pubkey,
+}
+
+impl Bench {
+    fn new() -> Self {
+        Bench {
+            admin: Keypair::new(),
+            gamer1: Pubkey::new_unique(),
+            gamer2: Pubkey::new_unique(),
+            referrer: Keypair::new(),
+            mint: Pubkey::new_unique(),
+        }
+    }
+
+    fn game(&self, case: Case) -> Game {
+        let mut game = joined_game(self.gamer1, self.gamer2, self.mint, STAKE);
+
+        match case {
+            Case::JoinGame => {
+                game.gamer2 = Pubkey::default();
+                game.token2 = Pubkey::default();
+                game.amount2 = 0;
+            }
+            Case::DistributeReferrers | Case::Distribute => {
+                // as `Settle` leaves it
+                game.closed = true;
+                game.winner = WINNER_GAMER1;
+                game.admin_fee = 100_000;
+                game.referrer_fee = 100_000;
+            }
+            _ => {}
+        }
+
+        game
+    }
+
+    fn program_test(&self, case: Case, before: bool) -> ProgramTest {
+        let mut program_test = if before {
+            ProgramTest::new(BEFORE, betting::id(), None)
+        } else {
+            program_test()
+        };
+
+        program_test.add_builtin_program("chainlink_store", chainlink_solana::id(), mock_chainlink);
+
+        fund(&mut program_test, &self.admin.pubkey());
+        fund(&mut program_test, &self.referrer.pubkey());
+
+        add_betting_info(&mut program_test, &betting_info(self.admin.pubkey()));
+        add_market(&mut program_test, &self.mint);
+
+        let game = self.game(case);
+
+        for (wallet, referrer) in [
+            (self.gamer1, self.referrer.pubkey()),
+            (self.gamer2, Pubkey::default()),
+        ]
+        .iter()
+        {
+            let mut user = user(*wallet, *referrer);
+            user.in_game = *wallet == game.gamer1 || *wallet == game.gamer2;
+            user.active_games = user.in_game as u64;
+            // a started game is only force-closed when one of its players is banned
+            user.banned = matches!(case, Case::ForcedClose) && *wallet == self.gamer2;
+            add_user(&mut program_test, &user);
+        }
+        add_user(
+            &mut program_test,
+            &user(self.referrer.pubkey(), Pubkey::default()),
+        );
+
+        add_game(&mut program_test, &game);
+        add_referral_reward(
+            &mut program_test,
+            &self.referrer.pubkey(),
+            &self.mint,
+            REWARD,
+        );
+
+        for owner in [self.admin.pubkey(), self.gamer1, self.gamer2].iter() {
+            add_token_account(&mut program_test, owner, &self.mint, 0);
+        }
+        add_token_account(
+            &mut program_test,
+            &self.referrer.pubkey(),
+            &self.mint,
+            100 * STAKE,
+        );
+        add_token_account(
+            &mut program_test,
+            &betting::pda::find_betting_address(&betting::id()).0,
+            &self.mint,
+            REWARD,
+        );
+
+        program_test
+    }
+
+    fn instruction(&self, case: Case) -> (Instruction, &Keypair) {
+        let program_id = &betting::id();
+        let admin = &self.admin.pubkey();
+        let referrer = &self.referrer.pubkey();
+        let game = &self.game(case);
+
+        match case {
+            Case::Bet => (
+                instruction::new_game(
+                    program_id,
+                    referrer,
+                    &self.mint,
+                    &chainlink_solana::id(),
+                    &feed_address(&self.mint),
+                    TYPE,
+                    false,
+                ),
+                &self.referrer,
+            ),
+            Case::JoinGame => (
+                instruction::join_game(
+                    program_id,
+                    referrer,
+                    self.gamer1,
+                    &self.mint,
+                    &chainlink_solana::id(),
+                    &feed_address(&self.mint),
+                    TYPE,
+                    false,
+                ),
+                &self.referrer,
+            ),
+            Case::SetLimits => (
+                instruction::set_limits(
+                    program_id,
+                    referrer,
+                    Limits {
+                        max_daily_games: 3,
+                        ..Limits::default()
+                    },
+                ),
+                &self.referrer,
+            ),
+            Case::SetTypePrice => (
+                instruction::set_type_price(program_id, admin, TYPE, 2 * TYPE_PRICE_VALUE),
+                &self.admin,
+            ),
+            Case::LockBets => (instruction::lock_bets(program_id, admin), &self.admin),
+            Case::BanUser => (
+                instruction::ban_user(program_id, admin, *referrer, 1, 0),
+                &self.admin,
+            ),
+            Case::ForcedClose => (
+                instruction::forced_close(program_id, admin, game),
+                &self.admin,
+            ),
+            Case::Settle => (
+                instruction::settle(program_id, admin, game, self.gamer1, TYPE),
+                &self.admin,
+            ),
+            Case::DistributeReferrers => (
+                instruction::distribute_referrers(program_id, admin, game, &[*referrer], &[]),
+                &self.admin,
+            ),
+            Case::Distribute => (
+                instruction::distribute(
+                    program_id,
+                    admin,
+                    self.gamer1,
+                    PAYOUT_WINNER,
+                    &self.gamer1,
+                    &self.mint,
+                ),
+                &self.admin,
+            ),
+            Case::ClaimReferralRewards => (
+                instruction::claim_referral_rewards(program_id, referrer, &self.mint),
+                &self.referrer,
+            ),
+            Case::Deregister => (
+                instruction::deregister(program_id, referrer),
+                &self.referrer,
+            ),
+        }
+    }
+
+    /// Whether `case` succeeds within `units`.
+    async fn fits(&self, case: Case, before: bool, units: u64) -> bool {
+        let mut program_test = self.program_test(case, before);
+        program_test.set_compute_max_units(units);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (instruction, signer) = self.instruction(case);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, signer],
+            recent_blockhash,
+        );
+
+        // running out of units isn't always a `TransactionError`, so any error counts
+        banks_client.process_transaction(transaction).await.is_ok()
+    }
+
+    /// The fewest compute units `case` succeeds with.
+    async fn units(&self, case: Case, before: bool) -> u64 {
+        assert!(self.fits(case, before, MAX_UNITS).await, "{:?} fails", case);
+
+        let (mut low, mut high) = (0, MAX_UNITS);
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if self.fits(case, before, mid).await {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+
+        high
+    }
+}
+
+#[tokio::test]
+async fn stored_bumps_save_compute_units() {
+    if std::env::var("BPF_OUT_DIR").is_err() {
+        println!("skipped: run with `make bench` to measure the BPF builds");
+        return;
+    }
+
+    let bench = Bench::new();
+
+    println!("{:<24}{:>10}{:>10}", "instruction", "before", "after");
+
+    for case in CASES.iter() {
+        let before = bench.units(*case, true).await;
+        let after = bench.units(*case, false).await;
+
+        println!("{:<24}{:>10}{:>10}", format!("{:?}", case), before, after);
+
+        assert!(after <= before, "{:?} got more expensive", case);
+    }
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use betting::consts::{MAX_CLOSE_DELAY, MAX_CONFIG_DELAY};
+use betting::error::ContractError;
+use betting::instruction;
+use betting::state::structs::{BettingInfo, Config};
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+fn config(betting_info: &BettingInfo) -> Config {
+    Config {
+        referrer_fee: betting_info.referrer_fee,
+        admin_fee: betting_info.admin_fee,
+        global_fee: betting_info.global_fee,
+        transaction_fee: betting_info.transaction_fee,
+        close_delay: betting_info.close_delay,
+        referral_tiers: betting_info.referral_tiers,
+        config_delay: betting_info.config_delay,
+    }
+}
+
+/// Queues `config` as `admin` against `betting_info`.
+async fn queue(
+    betting_info: BettingInfo,
+    admin: &Keypair,
+    config: Config,
+) -> Result<(), TransactionError> {
+    let mut program_test = program_test();
+    fund(&mut program_test, &admin.pubkey());
+    add_betting_info(&mut program_test, &betting_info);
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[instruction::queue_config_change(
+            &betting::id(),
+            &admin.pubkey(),
+            config,
+        )],
+        &[admin],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn delays_are_bounded() {
+    let admin = Keypair::new();
+    let betting_info = betting_info(admin.pubkey());
+
+    let at_most = Config {
+        close_delay: MAX_CLOSE_DELAY,
+        config_delay: MAX_CONFIG_DELAY,
+        ..config(&betting_info)
+    };
+    assert_eq!(
+        queue(betting_info.clone(), &admin, at_most.clone()).await,
+        Ok(())
+    );
+
+    for config in [
+        Config {
+            close_delay: MAX_CLOSE_DELAY + 1,
+            ..at_most.clone()
+        },
+        Config {
+            config_delay: MAX_CONFIG_DELAY + 1,
+            ..at_most.clone()
+        },
+        Config {
+            config_delay: u64::MAX,
+            ..at_most
+        },
+    ]
+    .iter()
+    {
+        assert_eq!(
+            queue(betting_info.clone(), &admin, config.clone()).await,
+            Err(contract_error(ContractError::InvalidDelays))
+        );
+    }
+}
+
+#[tokio::test]
+async fn timelock_overflow_is_rejected() {
+    let admin = Keypair::new();
+    let config = config(&betting_info(admin.pubkey()));
+    let betting_info = BettingInfo {
+        config_delay: u64::MAX,
+        ..betting_info(admin.pubkey())
+    };
+
+    assert_eq!(
+        queue(betting_info, &admin, config).await,
+        Err(contract_error(ContractError::OperationWithOverflow))
+    );
+}
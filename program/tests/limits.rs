@@ -0,0 +1,161 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use betting::consts::LIMITS_COOLING_OFF;
+use betting::instruction;
+use betting::state::structs::{Limits, User};
+use common::*;
+use solana_program::clock::Clock;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::BanksClient;
+use solana_sdk::signature::Signer;
+
+const THREE_GAMES: Limits = Limits {
+    max_type_price: 0,
+    max_daily_games: 3,
+    daily_loss_limit: 0,
+    excluded_until: 0,
+};
+
+/// Runs `SetLimits` with `limits` for a registered user in `state` and returns the user after
+/// it, with the time it ran at.
+async fn set_limits(state: impl Fn(&mut User), limits: Limits) -> (User, u64) {
+    let mut program_test = program_test();
+    let wallet = add_wallet(&mut program_test);
+
+    let mut user = user(wallet.pubkey(), Pubkey::default());
+    state(&mut user);
+    add_user(&mut program_test, &user);
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[instruction::set_limits(
+            &betting::id(),
+            &wallet.pubkey(),
+            limits,
+        )],
+        &[&wallet],
+    )
+    .await
+    .unwrap();
+
+    (
+        get_user(&mut banks_client, &wallet.pubkey()).await,
+        now(&mut banks_client).await,
+    )
+}
+
+async fn now(banks_client: &mut BanksClient) -> u64 {
+    banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp as u64
+}
+
+#[tokio::test]
+async fn tighter_limits_apply_at_once() {
+    let (user, _) = set_limits(|_| {}, THREE_GAMES).await;
+
+    assert_eq!(user.limits, THREE_GAMES);
+    assert_eq!(user.pending_limits_at, 0);
+}
+
+#[tokio::test]
+async fn looser_limits_wait_out_the_cooling_off() {
+    let (user, now) = set_limits(|user| user.limits = THREE_GAMES, Limits::default()).await;
+
+    assert_eq!(user.limits, THREE_GAMES);
+    assert_eq!(user.pending_limits, Limits::default());
+    assert_eq!(user.pending_limits_at, now + LIMITS_COOLING_OFF);
+}
+
+#[tokio::test]
+async fn queued_limits_apply_after_the_cooling_off() {
+    let (user, _) = set_limits(
+        |user| {
+            user.limits = THREE_GAMES;
+            user.pending_limits = Limits::default();
+            user.pending_limits_at = 1;
+        },
+        Limits::default(),
+    )
+    .await;
+
+    assert_eq!(user.limits, Limits::default());
+    assert_eq!(user.pending_limits_at, 0);
+}
+
+#[tokio::test]
+async fn loosening_again_restarts_the_cooling_off() {
+    let (user, now) = set_limits(
+        |user| {
+            user.limits = THREE_GAMES;
+            user.pending_limits = Limits {
+                max_daily_games: 5,
+                ..THREE_GAMES
+            };
+            user.pending_limits_at = u64::MAX;
+        },
+        Limits::default(),
+    )
+    .await;
+
+    assert_eq!(user.limits, THREE_GAMES);
+    assert_eq!(user.pending_limits, Limits::default());
+    assert_eq!(user.pending_limits_at, now + LIMITS_COOLING_OFF);
+}
+
+#[tokio::test]
+async fn keeping_the_limits_cancels_a_queued_loosening() {
+    let (user, _) = set_limits(
+        |user| {
+            user.limits = THREE_GAMES;
+            user.pending_limits = Limits::default();
+            user.pending_limits_at = u64::MAX;
+        },
+        THREE_GAMES,
+    )
+    .await;
+
+    assert_eq!(user.limits, THREE_GAMES);
+    assert_eq!(user.pending_limits, Limits::default());
+    assert_eq!(user.pending_limits_at, 0);
+}
+
+#[tokio::test]
+async fn an_expired_exclusion_is_not_a_loosening() {
+    let (user, _) = set_limits(|user| user.limits.excluded_until = 1, Limits::default()).await;
+
+    assert_eq!(user.limits, Limits::default());
+    assert_eq!(user.pending_limits_at, 0);
+}
+
+#[tokio::test]
+async fn a_running_exclusion_is_never_shortened() {
+    let (user, now) = set_limits(
+        |user| {
+            user.limits = Limits {
+                excluded_until: u64::MAX,
+                ..THREE_GAMES
+            }
+        },
+        Limits::default(),
+    )
+    .await;
+
+    assert_eq!(user.limits.excluded_until, u64::MAX);
+    assert_eq!(
+        user.pending_limits,
+        Limits {
+            excluded_until: u64::MAX,
+            ..Limits::default()
+        }
+    );
+    assert_eq!(user.pending_limits_at, now + LIMITS_COOLING_OFF);
+}
@@ -0,0 +1,339 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use betting::consts::{ACCOUNT_VERSION, DISCRIMINATOR_ACCOUNT_VERSION, RESERVED_SPACE, USER};
+use betting::error::ContractError;
+use betting::instruction;
+use betting::state::helpers::{GAME_LEN, USER_LEN};
+use betting::state::legacy::{GameV1, UserV0};
+use betting::state::structs::{AccountType, Limits, TokenStats};
+use borsh::BorshSerialize;
+use common::*;
+use solana_program::hash::hash;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::ProgramTest;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+const TURNOVER: u64 = 1_234;
+
+/// A registered user as it was written before versioning, password included.
+fn add_user_v0(program_test: &mut ProgramTest, wallet: &Keypair, referrer: Pubkey) {
+    let user = UserV0 {
+        address: wallet.pubkey(),
+        referrer,
+        in_game: false,
+        support_bots: true,
+        is_bot: false,
+        turnover: TURNOVER,
+        password: "password".to_string(),
+    };
+
+    add_account(
+        program_test,
+        user_address(&wallet.pubkey()),
+        betting::id(),
+        user.try_to_vec().unwrap(),
+    );
+}
+
+#[tokio::test]
+async fn old_users_have_to_be_migrated() {
+    let mut program_test = program_test();
+    let wallet = add_wallet(&mut program_test);
+    let referrer = Pubkey::new_unique();
+    add_user_v0(&mut program_test, &wallet, referrer);
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    let address = user_address(&wallet.pubkey());
+
+    assert_eq!(
+        process(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &[instruction::set_limits(
+                &betting::id(),
+                &wallet.pubkey(),
+                Limits::default(),
+            )],
+            &[&wallet],
+        )
+        .await,
+        Err(contract_error(ContractError::MigrationRequired))
+    );
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[instruction::migrate_account(
+            &betting::id(),
+            &payer.pubkey(),
+            &address,
+            AccountType::User,
+            0,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        get_data(&mut banks_client, address).await.unwrap().len(),
+        USER_LEN
+    );
+
+    let user = get_user(&mut banks_client, &wallet.pubkey()).await;
+    assert_eq!(user.version, DISCRIMINATOR_ACCOUNT_VERSION);
+    assert_eq!(user.address, wallet.pubkey());
+    assert_eq!(user.referrer, referrer);
+    assert!(user.support_bots);
+    assert_eq!(user.turnover, TURNOVER);
+    assert_eq!(user.credential_hash, None);
+    assert_eq!(
+        user.bump,
+        Pubkey::find_program_address(&[USER, &wallet.pubkey().to_bytes()], &betting::id()).1
+    );
+
+    // a second run finds nothing to do, and the account can be written again
+    assert_eq!(
+        process(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &[instruction::migrate_account(
+                &betting::id(),
+                &wallet.pubkey(),
+                &address,
+                AccountType::User,
+                0,
+            )],
+            &[&wallet],
+        )
+        .await,
+        Err(contract_error(ContractError::AccountUpToDate))
+    );
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[instruction::set_limits(
+            &betting::id(),
+            &wallet.pubkey(),
+            Limits {
+                max_daily_games: 3,
+                ..Limits::default()
+            },
+        )],
+        &[&wallet],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        get_user(&mut banks_client, &wallet.pubkey())
+            .await
+            .limits
+            .max_daily_games,
+        3
+    );
+}
+
+#[tokio::test]
+async fn games_keep_their_state_when_migrated() {
+    let mut program_test = program_test();
+    let gamer1 = Pubkey::new_unique();
+    let gamer2 = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+
+    let game = GameV1 {
+        account_type: AccountType::Game,
+        version: 1,
+        gamer1,
+        gamer2,
+        token1: mint,
+        token2: mint,
+        amount1: 10,
+        amount2: 20,
+        latest_bet: 30,
+        closed: true,
+        type_price: TYPE_PRICE_VALUE,
+        winner: 2,
+        admin_fee: 40,
+        referrer_fee: 50,
+        distributed: 1,
+        bump: 0,
+        reserved: [0; RESERVED_SPACE - 19],
+    };
+    add_account(
+        &mut program_test,
+        game_address(&gamer1),
+        betting::id(),
+        game.try_to_vec().unwrap(),
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[instruction::migrate_account(
+            &betting::id(),
+            &payer.pubkey(),
+            &game_address(&gamer1),
+            AccountType::Game,
+            0,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        get_data(&mut banks_client, game_address(&gamer1))
+            .await
+            .unwrap()
+            .len(),
+        GAME_LEN
+    );
+
+    let migrated = get_game(&mut banks_client, &gamer1).await;
+    assert_eq!(migrated.version, DISCRIMINATOR_ACCOUNT_VERSION);
+    assert_eq!(
+        (migrated.gamer1, migrated.gamer2, migrated.token1),
+        (gamer1, gamer2, mint)
+    );
+    assert_eq!(
+        (migrated.amount1, migrated.amount2, migrated.latest_bet),
+        (10, 20, 30)
+    );
+    assert!(migrated.closed);
+    assert_eq!(
+        (migrated.winner, migrated.distributed),
+        (game.winner, game.distributed)
+    );
+    assert_eq!((migrated.admin_fee, migrated.referrer_fee), (40, 50));
+    assert_eq!(migrated.bump, joined_game(gamer1, gamer2, mint, 0).bump);
+}
+
+#[tokio::test]
+async fn accounts_can_not_be_migrated_as_another_type() {
+    let mut program_test = program_test();
+    let wallet = add_wallet(&mut program_test);
+    add_user(&mut program_test, &user(wallet.pubkey(), Pubkey::default()));
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    assert_eq!(
+        process(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &[instruction::migrate_account(
+                &betting::id(),
+                &payer.pubkey(),
+                &user_address(&wallet.pubkey()),
+                AccountType::Game,
+                0,
+            )],
+            &[],
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData
+        ))
+    );
+}
+
+#[test]
+fn discriminators_are_the_hash_of_the_type_name() {
+    for (account_type, name) in [
+        (AccountType::BettingInfo, "BettingInfo"),
+        (AccountType::Game, "Game"),
+        (AccountType::User, "User"),
+        (AccountType::SupportedToken, "SupportedToken"),
+        (AccountType::TypePrice, "TypePrice"),
+        (AccountType::TokenStats, "TokenStats"),
+        (AccountType::ReferralCode, "ReferralCode"),
+        (AccountType::ReferralReward, "ReferralReward"),
+        (AccountType::Role, "Role"),
+        (AccountType::PendingConfig, "PendingConfig"),
+        (AccountType::BotInfo, "BotInfo"),
+        (AccountType::BotRegistry, "BotRegistry"),
+        (AccountType::Session, "Session"),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            account_type.discriminator()[..],
+            hash(format!("account:{}", name).as_bytes()).to_bytes()[..8]
+        );
+    }
+}
+
+#[tokio::test]
+async fn tagged_accounts_migrate_to_the_discriminator() {
+    let mut program_test = program_test();
+    let mint = Pubkey::new_unique();
+
+    // the stats as they were stored behind the one-byte tag
+    let stats = TokenStats {
+        discriminator: AccountType::TokenStats.discriminator(),
+        version: ACCOUNT_VERSION,
+        mint,
+        staked_volume: 10,
+        games_settled: 20,
+        admin_fees: 0,
+        referrer_fees: 0,
+        penalties: 0,
+        bump: 0,
+        reserved: [0; RESERVED_SPACE - 1],
+    };
+    let data = stats.try_to_vec().unwrap();
+    let tagged = [
+        &AccountType::TokenStats.try_to_vec().unwrap()[..],
+        &data[8..],
+    ]
+    .concat();
+    add_account(
+        &mut program_test,
+        stats_address(&mint),
+        betting::id(),
+        tagged.clone(),
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[instruction::migrate_account(
+            &betting::id(),
+            &payer.pubkey(),
+            &stats_address(&mint),
+            AccountType::TokenStats,
+            0,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let data = get_data(&mut banks_client, stats_address(&mint))
+        .await
+        .unwrap();
+    assert_eq!(data.len(), tagged.len() + 7);
+    assert_eq!(data[..8], AccountType::TokenStats.discriminator());
+
+    let migrated = get_stats(&mut banks_client, &mint).await;
+    assert_eq!(migrated.version, DISCRIMINATOR_ACCOUNT_VERSION);
+    assert_eq!((migrated.mint, migrated.staked_volume), (mint, 10));
+    assert_eq!(migrated.games_settled, 20);
+}
@@ -0,0 +1,178 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use betting::error::ContractError;
+use betting::instruction::{self, Referrer};
+use betting::state::structs::{Limits, User};
+use common::*;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn register(wallet: &Keypair, referrer: Pubkey) -> Instruction {
+    instruction::registration(
+        &betting::id(),
+        &wallet.pubkey(),
+        Referrer::Wallet(referrer),
+        None,
+    )
+}
+
+#[tokio::test]
+async fn registration_fails_for_registered_users() {
+    let referrer = Pubkey::new_unique();
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[register(&payer, referrer)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        process(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &[register(&payer, Pubkey::new_unique())],
+            &[],
+        )
+        .await,
+        Err(contract_error(ContractError::AlreadyRegistered))
+    );
+
+    let user = get_user(&mut banks_client, &payer.pubkey()).await;
+    assert_eq!(user.address, payer.pubkey());
+    assert_eq!(user.referrer, referrer);
+}
+
+#[tokio::test]
+async fn deregistered_users_can_register_again() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[
+            register(&payer, Pubkey::default()),
+            instruction::deregister(&betting::id(), &payer.pubkey()),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert!(get_data(&mut banks_client, user_address(&payer.pubkey()))
+        .await
+        .is_none());
+
+    let referrer = Pubkey::new_unique();
+
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[register(&payer, referrer)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        get_user(&mut banks_client, &payer.pubkey()).await.referrer,
+        referrer
+    );
+}
+
+/// A registered user in `state` tries to close their account.
+async fn deregister(state: impl Fn(&mut User)) -> Result<(), ()> {
+    let mut program_test = program_test();
+    let wallet = add_wallet(&mut program_test);
+
+    let mut user = user(wallet.pubkey(), Pubkey::new_unique());
+    state(&mut user);
+    add_user(&mut program_test, &user);
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let result = process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[instruction::deregister(&betting::id(), &wallet.pubkey())],
+        &[&wallet],
+    )
+    .await;
+
+    let data = get_data(&mut banks_client, user_address(&wallet.pubkey())).await;
+
+    match result {
+        Ok(()) => {
+            assert!(data.is_none());
+            Ok(())
+        }
+        Err(err) => {
+            assert_eq!(err, contract_error(ContractError::LimitsActive));
+            assert!(data.is_some());
+            Err(())
+        }
+    }
+}
+
+#[tokio::test]
+async fn deregister_keeps_limits_in_effect() {
+    assert_eq!(deregister(|_| {}).await, Ok(()));
+
+    // a self-exclusion that has run out no longer counts
+    assert_eq!(
+        deregister(|user| user.limits.excluded_until = 1).await,
+        Ok(())
+    );
+
+    assert_eq!(
+        deregister(|user| user.limits.excluded_until = u64::MAX).await,
+        Err(())
+    );
+    assert_eq!(
+        deregister(|user| user.limits.max_daily_games = 3).await,
+        Err(())
+    );
+    assert_eq!(
+        deregister(|user| {
+            user.pending_limits = Limits::default();
+            user.pending_limits_at = u64::MAX;
+        })
+        .await,
+        Err(())
+    );
+}
+
+#[tokio::test]
+async fn banned_users_can_not_deregister() {
+    let mut program_test = program_test();
+    let wallet = add_wallet(&mut program_test);
+
+    let mut user = user(wallet.pubkey(), Pubkey::new_unique());
+    user.banned = true;
+    add_user(&mut program_test, &user);
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    assert_eq!(
+        process(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &[instruction::deregister(&betting::id(), &wallet.pubkey())],
+            &[&wallet],
+        )
+        .await,
+        Err(contract_error(ContractError::UserBanned))
+    );
+}
@@ -0,0 +1,377 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use betting::consts::{
+    PAYOUT_ADMIN, PAYOUT_REFERRERS, PAYOUT_WINNER, PAYOUT_WINNER2, WINNER_GAMER1,
+};
+use betting::error::ContractError;
+use betting::instruction;
+use betting::pda::find_betting_address;
+use common::*;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{BanksClient, ProgramTest};
+use solana_sdk::hash::Hash;
+use solana_sdk::signature::{Keypair, Signer};
+
+const STAKE: u64 = 1_000_000;
+
+struct Players {
+    admin: Keypair,
+    gamer1: Pubkey,
+    gamer2: Pubkey,
+    referrer: Pubkey,
+    mint: Pubkey,
+}
+
+/// A joined game between a referred and an unreferred player; the referrer has opened their
+/// reward PDA when `reward_opened`.
+fn joined(reward_opened: bool) -> (ProgramTest, Players) {
+    let mut program_test = program_test();
+
+    let players = Players {
+        admin: add_wallet(&mut program_test),
+        gamer1: Pubkey::new_unique(),
+        gamer2: Pubkey::new_unique(),
+        referrer: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+    };
+
+    add_betting_info(&mut program_test, &betting_info(players.admin.pubkey()));
+    add_market(&mut program_test, &players.mint);
+
+    for (wallet, referrer) in [
+        (players.gamer1, players.referrer),
+        (players.gamer2, Pubkey::default()),
+    ]
+    .iter()
+    {
+        let mut user = user(*wallet, *referrer);
+        user.in_game = true;
+        user.active_games = 1;
+        add_user(&mut program_test, &user);
+    }
+    add_user(
+        &mut program_test,
+        &user(players.referrer, Pubkey::default()),
+    );
+
+    add_game(
+        &mut program_test,
+        &joined_game(players.gamer1, players.gamer2, players.mint, STAKE),
+    );
+
+    if reward_opened {
+        add_referral_reward(&mut program_test, &players.referrer, &players.mint, 0);
+    }
+
+    for owner in [
+        players.admin.pubkey(),
+        players.gamer1,
+        find_betting_address(&betting::id()).0,
+    ]
+    .iter()
+    {
+        add_token_account(&mut program_test, owner, &players.mint, 0);
+    }
+
+    (program_test, players)
+}
+
+async fn settle(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    players: &Players,
+) {
+    let game = get_game(banks_client, &players.gamer1).await;
+
+    process(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &[instruction::settle(
+            &betting::id(),
+            &players.admin.pubkey(),
+            &game,
+            players.gamer1,
+            TYPE,
+        )],
+        &[&players.admin],
+    )
+    .await
+    .unwrap();
+}
+
+async fn distribute(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    players: &Players,
+    index: u8,
+) -> Result<(), solana_sdk::transaction::TransactionError> {
+    let game = get_game(banks_client, &players.gamer1).await;
+
+    let instruction = match index {
+        PAYOUT_REFERRERS => instruction::distribute_referrers(
+            &betting::id(),
+            &payer.pubkey(),
+            &game,
+            &[players.referrer],
+            &[],
+        ),
+        PAYOUT_ADMIN => instruction::distribute(
+            &betting::id(),
+            &payer.pubkey(),
+            players.gamer1,
+            index,
+            &players.admin.pubkey(),
+            &players.mint,
+        ),
+        _ => instruction::distribute(
+            &betting::id(),
+            &payer.pubkey(),
+            players.gamer1,
+            index,
+            &players.gamer1,
+            &players.mint,
+        ),
+    };
+
+    process(banks_client, payer, recent_blockhash, &[instruction], &[]).await
+}
+
+#[tokio::test]
+async fn settle_moves_no_tokens() {
+    let (program_test, players) = joined(true);
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    settle(&mut banks_client, &payer, recent_blockhash, &players).await;
+
+    let game = get_game(&mut banks_client, &players.gamer1).await;
+    assert!(game.closed);
+    assert_eq!(game.winner, WINNER_GAMER1);
+    assert_eq!(game.distributed, 0);
+    // 10% of both stakes, half of it to the admin and half into the referral pool
+    assert_eq!(game.admin_fee, 100_000);
+    assert_eq!(game.referrer_fee, 100_000);
+
+    assert_eq!(
+        get_token_amount(
+            &mut banks_client,
+            &game_address(&players.gamer1),
+            &players.mint
+        )
+        .await,
+        2 * STAKE
+    );
+    assert_eq!(
+        get_referral_reward(&mut banks_client, &players.referrer, &players.mint)
+            .await
+            .balance,
+        0
+    );
+
+    let stats = get_stats(&mut banks_client, &players.mint).await;
+    assert_eq!(stats.games_settled, 1);
+    assert_eq!(stats.referrer_fees, 0);
+
+    let user = get_user(&mut banks_client, &players.gamer1).await;
+    assert!(!user.in_game);
+    assert_eq!(user.wins, 1);
+}
+
+#[tokio::test]
+async fn admin_payout_waits_for_referrers() {
+    let (program_test, players) = joined(true);
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    settle(&mut banks_client, &payer, recent_blockhash, &players).await;
+
+    assert_eq!(
+        distribute(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &players,
+            PAYOUT_ADMIN
+        )
+        .await,
+        Err(contract_error(ContractError::ReferrerPayoutPending))
+    );
+}
+
+#[tokio::test]
+async fn referral_payout_accrues_what_reaches_the_vault() {
+    let (program_test, players) = joined(true);
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    settle(&mut banks_client, &payer, recent_blockhash, &players).await;
+
+    distribute(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &players,
+        PAYOUT_REFERRERS,
+    )
+    .await
+    .unwrap();
+
+    // the first tier takes the whole pool of gamer1; gamer2 has no referrer
+    let betting = find_betting_address(&betting::id()).0;
+    assert_eq!(
+        get_token_amount(&mut banks_client, &betting, &players.mint).await,
+        50_000
+    );
+    assert_eq!(
+        get_referral_reward(&mut banks_client, &players.referrer, &players.mint)
+            .await
+            .balance,
+        50_000
+    );
+
+    let game = get_game(&mut banks_client, &players.gamer1).await;
+    assert_eq!(game.referrer_fee, 50_000);
+    assert_eq!(game.admin_fee, 150_000);
+
+    let stats = get_stats(&mut banks_client, &players.mint).await;
+    assert_eq!(stats.referrer_fees, 50_000);
+    assert_eq!(stats.admin_fees, 150_000);
+
+    for index in [PAYOUT_ADMIN, PAYOUT_WINNER, PAYOUT_WINNER2].iter() {
+        distribute(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &players,
+            *index,
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(
+        get_token_amount(&mut banks_client, &players.admin.pubkey(), &players.mint).await,
+        150_000
+    );
+    assert_eq!(
+        get_token_amount(&mut banks_client, &players.gamer1, &players.mint).await,
+        2 * STAKE - 200_000
+    );
+    assert_eq!(
+        get_token_amount(
+            &mut banks_client,
+            &game_address(&players.gamer1),
+            &players.mint
+        )
+        .await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn referral_payout_opens_missing_reward_pdas() {
+    let (program_test, players) = joined(false);
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    settle(&mut banks_client, &payer, recent_blockhash, &players).await;
+
+    distribute(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &players,
+        PAYOUT_REFERRERS,
+    )
+    .await
+    .unwrap();
+
+    let reward = get_referral_reward(&mut banks_client, &players.referrer, &players.mint).await;
+    assert_eq!(reward.referrer, players.referrer);
+    assert_eq!(reward.balance, 50_000);
+
+    let game = get_game(&mut banks_client, &players.gamer1).await;
+    assert_eq!(game.referrer_fee, 50_000);
+    assert_eq!(game.admin_fee, 150_000);
+
+    let betting = find_betting_address(&betting::id()).0;
+    assert_eq!(
+        get_token_amount(&mut banks_client, &betting, &players.mint).await,
+        50_000
+    );
+}
+
+#[tokio::test]
+async fn payouts_are_made_once() {
+    let (program_test, players) = joined(true);
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    settle(&mut banks_client, &payer, recent_blockhash, &players).await;
+
+    distribute(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &players,
+        PAYOUT_WINNER,
+    )
+    .await
+    .unwrap();
+
+    // pushed by someone else, so it isn't the same transaction
+    let pusher = Keypair::new();
+    let game = get_game(&mut banks_client, &players.gamer1).await;
+
+    assert_eq!(
+        process(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &[instruction::distribute(
+                &betting::id(),
+                &pusher.pubkey(),
+                game.gamer1,
+                PAYOUT_WINNER,
+                &players.gamer1,
+                &players.mint,
+            )],
+            &[&pusher],
+        )
+        .await,
+        Err(contract_error(ContractError::PayoutDistributed))
+    );
+}
+
+#[tokio::test]
+async fn started_games_can_not_be_joined() {
+    let (mut program_test, players) = joined(true);
+    let latecomer = add_wallet(&mut program_test);
+    add_user(
+        &mut program_test,
+        &user(latecomer.pubkey(), Pubkey::default()),
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    assert_eq!(
+        process(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            &[instruction::join_game(
+                &betting::id(),
+                &latecomer.pubkey(),
+                players.gamer1,
+                &players.mint,
+                &chainlink_solana::id(),
+                &feed_address(&players.mint),
+                TYPE,
+                false,
+            )],
+            &[&latecomer],
+        )
+        .await,
+        Err(contract_error(ContractError::GameAlreadyStarted))
+    );
+}